@@ -4,7 +4,7 @@ use bevy_text_span_entities::prelude::*;
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(TsePlugin)
+        .add_plugins(TsePlugin::default())
         .add_systems(Startup, setup)
         .add_systems(
             Update,
@@ -144,7 +144,7 @@ fn initialise_hover(mut query: Query<(&mut TextSpan, &HoverConfig), Added<HoverC
 #[allow(clippy::type_complexity)]
 fn notify_hovered(
     In(hit): In<Option<Entity>>,
-    mut spans: Query<&mut HoverNotifier, (With<Parent>, With<HoverConfig>)>,
+    mut spans: Query<&mut HoverNotifier, (With<SpanOf>, With<HoverConfig>)>,
 ) {
     let Some(entity) = hit else {
         return;
@@ -182,7 +182,7 @@ fn hover_effect(
 fn notify_navigate(
     In(hit): In<Option<Entity>>,
     mouse_button: Res<ButtonInput<MouseButton>>,
-    mut spans: Query<&mut LinkNotifier, With<Parent>>,
+    mut spans: Query<&mut LinkNotifier, With<SpanOf>>,
 ) {
     if !mouse_button.just_pressed(MouseButton::Left) {
         return;
@@ -256,7 +256,7 @@ struct HitSystemParams<'w, 's> {
         (
             &'static CosmicBuffer,
             &'static GlobalTransform,
-            &'static Children,
+            &'static SpanEntities,
         ),
         With<Node>,
     >,
@@ -269,7 +269,7 @@ fn hit(params: HitSystemParams) -> Option<Entity> {
 
     let cursor_window_position = window.cursor_position()?;
 
-    for (buffer, transform, children) in &params.buffers {
+    for (buffer, transform, spans) in &params.buffers {
         let size = buffer.size();
         let size = Vec2::new(
             size.0.expect("Buffer has a width"),
@@ -289,8 +289,8 @@ fn hit(params: HitSystemParams) -> Option<Entity> {
                 let line = &buffer.lines[text_cursor.line];
                 let attrs = line.attrs_list().get_span(text_cursor.index);
                 let span_index = attrs.metadata;
-                // notify only the relevant child
-                return Some(children[span_index]);
+                // notify only the relevant span
+                return spans.get(span_index);
             }
         }
     }
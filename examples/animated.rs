@@ -4,7 +4,7 @@ use bevy_text_span_entities::prelude::*;
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(TsePlugin)
+        .add_plugins(TsePlugin::default())
         .add_systems(Startup, setup)
         .add_systems(Update, (iridescence, oscillate))
         .run();
@@ -1,67 +1,2437 @@
 pub mod prelude {
     pub use crate::helper::{text, text2d};
-    pub use crate::lib::{TextSpan, TextSpans, TsePlugin};
+    pub use crate::lib::{
+        lint_spans, validate_spans, FlushSpans, SinkRegistration, SpanCommandsExt, SpanDecoration,
+        SpanEntities, SpanGroupBuilder, SpanIssue, SpanIssueKind, SpanOf, SpanOrder, SpanReport,
+        SpanSpawner, SpanStyle, SpanSyncError, SpanSyncErrorKind, SpanSyncPolicy, SpanSyncRequest,
+        SpanValue, SpanVisibilityMode, SpanWorldExt, Spans, SyncBackend, SyncErrorPolicy,
+        SyncSpans, TextSink, TextSpan, TextSpans, TextSpans2d, TextSpansBuilder, TextSpansUi,
+        TseDiagnosticsPlugin, TsePlugin, TseSet, TseWorldExt, ValidateSpans,
+    };
 }
 
 mod lib {
 
-    use bevy::prelude::*;
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::Arc,
+        time::Duration,
+    };
 
-    pub struct TsePlugin;
+    use bevy::{
+        diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+        ecs::{
+            component::{ComponentHooks, ComponentId, StorageType, Tick},
+            entity::{EntityMapper, MapEntities},
+            reflect::ReflectMapEntities,
+            schedule::{InternedScheduleLabel, ScheduleLabel},
+            system::{EntityCommands, SystemChangeTick, SystemParam, SystemState},
+            world::{Command, DeferredWorld},
+        },
+        prelude::*,
+        sprite::Anchor,
+        tasks::ComputeTaskPool,
+        text::Text2dBounds,
+        ui::FocusPolicy,
+        utils::Instant,
+    };
+
+    /// Syncs `TextSpan` children into the `Text` of their `TextSpans` parent.
+    pub struct TsePlugin {
+        /// The schedule the [`TseSet`]s run in. Defaults to [`PostUpdate`], before text layout.
+        pub schedule: InternedScheduleLabel,
+        /// How parents whose spans were added, removed or moved are found.
+        pub backend: SyncBackend,
+        /// What to do, besides sending a [`SpanSyncError`], when a span hierarchy is invalid.
+        pub error_policy: SyncErrorPolicy,
+        /// Whether direct edits of `Text.sections` on a `TextSpans` parent are propagated back
+        /// to its spans, instead of being overwritten by the next sync.
+        pub reverse_sync: bool,
+        /// The [`TextSink`] components spans are flattened into. Defaults to just [`Text`].
+        pub sinks: Vec<SinkRegistration>,
+    }
+
+    /// A text-like component that the spans of a `TextSpans` parent are flattened into.
+    ///
+    /// Implemented for [`Text`]. Other sinks are added with [`TsePlugin::with_sink`],
+    /// a parent is synced into every sink component it has.
+    pub trait TextSink: Component {
+        /// The flattened sections, in span order
+        fn sections(&self) -> &[TextSection];
+
+        /// The flattened sections, for the sync to write
+        fn sections_mut(&mut self) -> &mut Vec<TextSection>;
+    }
+
+    impl TextSink for Text {
+        fn sections(&self) -> &[TextSection] {
+            &self.sections
+        }
+
+        fn sections_mut(&mut self) -> &mut Vec<TextSection> {
+            &mut self.sections
+        }
+    }
+
+    /// The [`TextSink`] components added to [`TsePlugin`], for [`validate_spans`].
+    #[derive(Resource, Debug, Default)]
+    struct TextSinkIds(Vec<ComponentId>);
+
+    /// Adds the systems syncing one [`TextSink`] component, see [`TsePlugin::with_sink`].
+    #[derive(Debug, Clone, Copy)]
+    pub struct SinkRegistration(fn(&mut App, &TsePlugin));
+
+    impl SinkRegistration {
+        /// The systems syncing the sink `T`
+        pub fn of<T: TextSink>() -> Self {
+            Self(add_sink_systems::<T>)
+        }
+    }
+
+    fn add_sink_systems<T: TextSink>(app: &mut App, plugin: &TsePlugin) {
+        let id = app.world_mut().init_component::<T>();
+        app.world_mut()
+            .get_resource_or_insert_with(TextSinkIds::default)
+            .0
+            .push(id);
+        add_sync_systems(app, plugin, || {
+            (mark_added_sinks::<T>, update_parent::<T>)
+                .chain()
+                .after(collect_edits)
+                .before(finish_sync)
+        });
+        if plugin.reverse_sync {
+            add_sync_systems(app, plugin, || {
+                reverse_sync::<T>
+                    .after(poll_structure)
+                    .before(collect_edits)
+            });
+        }
+    }
+
+    /// Adds the sync systems made by `systems` to [`TseSet::Sync`] of [`TsePlugin::schedule`],
+    /// and to [`SyncSpans`].
+    fn add_sync_systems<M, S: IntoSystemConfigs<M>>(
+        app: &mut App,
+        plugin: &TsePlugin,
+        systems: impl Fn() -> S,
+    ) {
+        app.add_systems(plugin.schedule, systems().in_set(TseSet::Sync));
+        app.add_systems(SyncSpans, systems());
+    }
+
+    /// How [`TsePlugin`] finds the parents whose span structure changed.
+    ///
+    /// In-place edits through `DerefMut` (of a `TextSpan`, `SpanStyle`, `Visibility` or
+    /// `Parent`) can't be observed, so both backends find those through change detection.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum SyncBackend {
+        /// Polls `Changed<Children>` and `RemovedComponents` on every sync.
+        #[default]
+        Polling,
+        /// Marks parents dirty from observers, the moment a `TextSpan`, `TextSpans`, `Parent`,
+        /// `SpanStyle`, `Visibility`, `SpanVisibilityMode`, `SpanOrder`, `SpanValue` or `SpanOf` is
+        /// inserted or removed.
+        ///
+        /// Reordering `Children` in place (e.g. sorting them) isn't observed.
+        Observers,
+    }
+
+    impl TsePlugin {
+        /// Runs the span sync in `schedule` instead of [`PostUpdate`].
+        pub fn in_schedule(schedule: impl ScheduleLabel) -> Self {
+            Self {
+                schedule: schedule.intern(),
+                ..Default::default()
+            }
+        }
+
+        /// Also flattens spans into the [`TextSink`] `T`, on parents that have one.
+        pub fn with_sink<T: TextSink>(mut self) -> Self {
+            self.sinks.push(SinkRegistration::of::<T>());
+            self
+        }
+    }
+
+    impl Default for TsePlugin {
+        fn default() -> Self {
+            Self {
+                schedule: PostUpdate.intern(),
+                backend: SyncBackend::default(),
+                error_policy: SyncErrorPolicy::default(),
+                reverse_sync: false,
+                sinks: vec![SinkRegistration::of::<Text>()],
+            }
+        }
+    }
+
+    fn observe_structure_of<C: Component>(app: &mut App) {
+        app.observe(observe_structure::<OnInsert, C>);
+        app.observe(observe_structure::<OnRemove, C>);
+    }
+
+    /// Records the cost of the span sync to the [`DiagnosticsStore`](bevy::diagnostic::DiagnosticsStore),
+    /// e.g. for `LogDiagnosticsPlugin`.
+    ///
+    /// Needs [`TsePlugin`]. Every measurement is of the last sync before [`Last`].
+    #[derive(Default)]
+    pub struct TseDiagnosticsPlugin;
+
+    impl Plugin for TseDiagnosticsPlugin {
+        fn build(&self, app: &mut App) {
+            app.register_diagnostic(Diagnostic::new(Self::DIRTY_PARENTS))
+                .register_diagnostic(Diagnostic::new(Self::REBUILT_SECTIONS))
+                .register_diagnostic(Diagnostic::new(Self::CLONED_BYTES).with_suffix("B"))
+                .register_diagnostic(Diagnostic::new(Self::SPANS))
+                .register_diagnostic(Diagnostic::new(Self::SYNC_TIME).with_suffix("ms"))
+                .add_systems(Last, Self::diagnostic_system);
+        }
+    }
+
+    impl TseDiagnosticsPlugin {
+        /// Parents walked again, because their span structure, styles or visibility changed
+        pub const DIRTY_PARENTS: DiagnosticPath =
+            DiagnosticPath::const_new("text_spans/dirty_parents");
+        /// Sections written, by walking parents again or patching changed spans
+        pub const REBUILT_SECTIONS: DiagnosticPath =
+            DiagnosticPath::const_new("text_spans/rebuilt_sections");
+        /// Bytes of span values copied into sections
+        pub const CLONED_BYTES: DiagnosticPath =
+            DiagnosticPath::const_new("text_spans/cloned_bytes");
+        /// Span entities synced into a parent
+        pub const SPANS: DiagnosticPath = DiagnosticPath::const_new("text_spans/spans");
+        /// Time spent syncing parents
+        pub const SYNC_TIME: DiagnosticPath = DiagnosticPath::const_new("text_spans/sync_time");
+
+        fn diagnostic_system(mut diagnostics: Diagnostics, layouts: Res<SpanLayouts>) {
+            let stats = layouts.stats;
+            diagnostics.add_measurement(&Self::DIRTY_PARENTS, || stats.dirty_parents as f64);
+            diagnostics.add_measurement(&Self::REBUILT_SECTIONS, || stats.rebuilt_sections as f64);
+            diagnostics.add_measurement(&Self::CLONED_BYTES, || stats.cloned_bytes as f64);
+            diagnostics.add_measurement(&Self::SPANS, || layouts.spans.len() as f64);
+            diagnostics.add_measurement(&Self::SYNC_TIME, || stats.time.as_secs_f64() * 1000.0);
+        }
+    }
+
+    /// A schedule with the same systems as [`TseSet::Sync`], to flatten changed spans on demand
+    /// through [`TseWorldExt::flush_spans`].
+    ///
+    /// It is not run by [`TsePlugin`] itself, so the sync in [`TsePlugin::schedule`] keeps
+    /// running in parallel with other systems.
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct SyncSpans;
+
+    /// Spawns span hierarchies and runs the span sync outside of its schedule.
+    pub trait TseWorldExt {
+        /// Flattens every changed `TextSpans` parent now, so its `Text` can be read in the same
+        /// system. Needs [`TsePlugin`].
+        fn flush_spans(&mut self);
+
+        /// Spawns a `TextSpans` parent with `bundle` (e.g. a [`TextBundle`]), and `spans`
+        /// linked to it through [`SpanOf`], in order.
+        ///
+        /// The span entities are in its [`Spans`] right away.
+        fn spawn_text_spans(
+            &mut self,
+            bundle: impl Bundle,
+            spans: impl IntoIterator<Item = TextSpan>,
+        ) -> EntityWorldMut<'_>;
+    }
+
+    impl TseWorldExt for World {
+        fn flush_spans(&mut self) {
+            self.run_schedule(SyncSpans);
+        }
+
+        fn spawn_text_spans(
+            &mut self,
+            bundle: impl Bundle,
+            spans: impl IntoIterator<Item = TextSpan>,
+        ) -> EntityWorldMut<'_> {
+            let root = self.spawn((bundle, TextSpans)).id();
+            for span in spans {
+                self.spawn((span, SpanOf(root)));
+            }
+            self.flush();
+            self.entity_mut(root)
+        }
+    }
+
+    /// A [`Command`] that runs [`TseWorldExt::flush_spans`], after the commands before it.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct FlushSpans;
+
+    impl Command for FlushSpans {
+        fn apply(self, world: &mut World) {
+            world.flush_spans();
+        }
+    }
+
+    /// The phases of the span sync, in order.
+    ///
+    /// Systems that edit spans late in the frame should run `.before(TseSet::Sync)`,
+    /// systems that read the flattened `Text` should run in [`TseSet::PostSync`].
+    #[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum TseSet {
+        /// Flattens changed `TextSpan` children into the `Text` of their parent
+        Sync,
+        /// After the sync, before text layout
+        PostSync,
+    }
 
     impl Plugin for TsePlugin {
         fn build(&self, app: &mut App) {
             app.register_type::<TextSpans>();
+            app.register_type::<TextSpansUi>();
+            app.register_type::<TextSpans2d>();
             app.register_type::<TextSpan>();
-            app.add_systems(
-                PostUpdate,
-                update_parent
+            app.register_type::<SpanEntities>();
+            app.register_type::<SpanStyle>();
+            app.register_type::<SpanVisibilityMode>();
+            app.register_type::<SpanDecoration>();
+            app.register_type::<SpanOrder>();
+            app.register_type::<SpanOf>();
+            app.register_type::<Spans>();
+            app.register_type::<SpanSyncPolicy>();
+            app.register_type::<SpanSyncRequest>();
+            app.register_type::<SpanValue>();
+            app.init_resource::<SpanLayouts>();
+            app.init_resource::<SpanLinks>();
+            app.insert_resource(self.error_policy);
+            app.add_event::<SpanSyncError>();
+            app.observe(insert_ui_text);
+            app.observe(insert_2d_text);
+            // The layout systems only exist with the UI and 2D text plugins,
+            // ordering against missing systems is a no-op.
+            app.configure_sets(
+                self.schedule,
+                (TseSet::Sync, TseSet::PostSync)
+                    .chain()
                     .before(bevy::ui::widget::measure_text_system)
                     .before(bevy::text::update_text2d_layout),
             );
+            add_sync_systems(app, self, || (collect_edits, finish_sync).chain());
+            add_sync_systems(app, self, || {
+                relink_spans
+                    .run_if(spans_relinked)
+                    .before(poll_structure)
+                    .before(collect_edits)
+            });
+            for sink in &self.sinks {
+                (sink.0)(app, self);
+            }
+            match self.backend {
+                SyncBackend::Polling => {
+                    add_sync_systems(app, self, || poll_structure.before(collect_edits));
+                }
+                SyncBackend::Observers => {
+                    observe_structure_of::<TextSpan>(app);
+                    observe_structure_of::<TextSpans>(app);
+                    observe_structure_of::<Parent>(app);
+                    observe_structure_of::<SpanStyle>(app);
+                    observe_structure_of::<Visibility>(app);
+                    observe_structure_of::<SpanVisibilityMode>(app);
+                    observe_structure_of::<SpanOrder>(app);
+                    observe_structure_of::<SpanValue>(app);
+                    observe_structure_of::<SpanOf>(app);
+                }
+            }
+        }
+    }
+
+    /// Sent when the sync finds an invalid span hierarchy.
+    #[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SpanSyncError {
+        /// The `TextSpans` parent being synced
+        pub parent: Entity,
+        /// What is invalid about its hierarchy
+        pub kind: SpanSyncErrorKind,
+    }
+
+    /// What is invalid about the span hierarchy of a [`SpanSyncError`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SpanSyncErrorKind {
+        /// The parent has no `Text` (or other [`TextSink`]) to sync into
+        MissingText,
+        /// A descendant of the parent is neither a `TextSpan`, a group (with a `SpanStyle`),
+        /// an embedded `TextSpans` parent, nor a [`SpanDecoration`]
+        MissingTextSpan { child: Entity },
+    }
+
+    impl std::fmt::Display for SpanSyncError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let parent = self.parent;
+            match self.kind {
+                SpanSyncErrorKind::MissingText => write!(f, "Missing `Text` for parent {parent:?}"),
+                SpanSyncErrorKind::MissingTextSpan { child } => {
+                    write!(
+                        f,
+                        "Missing `TextSpan` for child {child:?} for parent {parent:?}"
+                    )
+                }
+            }
+        }
+    }
+
+    /// What the sync does when it finds an invalid span hierarchy, after sending a
+    /// [`SpanSyncError`].
+    #[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum SyncErrorPolicy {
+        /// Logs the error
+        #[default]
+        Log,
+        /// Does nothing
+        Ignore,
+        /// Panics in debug builds, logs the error in release builds
+        PanicInDebug,
+        /// Fixes the hierarchy: inserts a default `Text` on the parent,
+        /// and marks children without a `TextSpan` as [`SpanDecoration`]s
+        ///
+        /// The inserted `Text` is synced but only renders if the parent is a UI node or has a
+        /// `Text2dBounds`; spawn parents with [`TextSpansUi`] or [`TextSpans2d`] to get the
+        /// rest of a text bundle.
+        Repair,
+    }
+
+    /// Opts a child of a `TextSpans` parent (and its descendants) out of the sync,
+    /// e.g. for icons or highlight boxes, instead of being reported as a missing `TextSpan`.
+    #[derive(Component, Debug, Clone, Default, Reflect)]
+    #[reflect(Component, Default)]
+    pub struct SpanDecoration;
+
+    /// The parent
+    ///
+    /// A `TextSpans` parent can be embedded below another one, e.g. as a reusable fragment:
+    /// its spans are then flattened inline into the enclosing text, and it needs no `Text` of
+    /// its own.
+    #[derive(Component, Debug, Clone, Default, Reflect)]
+    #[reflect(Component, Default)]
+    pub struct TextSpans;
+
+    /// A `TextSpans` parent rendered as screen-space UI text.
+    ///
+    /// When added, inserts `TextSpans` and the components of a [`TextBundle`] the entity doesn't
+    /// have yet, converting it from [`TextSpans2d`] if needed: only the components specific to
+    /// 2D text are removed. Components it already has, such as its `Style`, `Text` or span
+    /// children, are kept.
+    #[derive(Component, Debug, Clone, Default, Reflect)]
+    #[reflect(Component, Default)]
+    pub struct TextSpansUi;
+
+    /// A `TextSpans` parent rendered as world-space 2D text.
+    ///
+    /// When added, inserts `TextSpans` and the components of a [`Text2dBundle`] the entity
+    /// doesn't have yet, converting it from [`TextSpansUi`] if needed: only the components
+    /// specific to UI text are removed. Components it already has, such as its `Transform`,
+    /// `Text` or span children, are kept.
+    #[derive(Component, Debug, Clone, Default, Reflect)]
+    #[reflect(Component, Default)]
+    pub struct TextSpans2d;
+
+    fn insert_ui_text(trigger: Trigger<OnAdd, TextSpansUi>, mut commands: Commands) {
+        let entity = trigger.entity();
+        commands
+            .entity(entity)
+            .remove::<TextSpans2d>()
+            .insert(TextSpans);
+        commands.add(move |world: &mut World| {
+            complete_text::<TextBundle, Text2dBundle>(
+                world,
+                entity,
+                &[
+                    take_kept::<Text>,
+                    take_kept::<Style>,
+                    take_kept::<Transform>,
+                    take_kept::<Visibility>,
+                    take_kept::<FocusPolicy>,
+                    take_kept::<ZIndex>,
+                    take_kept::<BackgroundColor>,
+                ],
+            );
+        });
+    }
+
+    fn insert_2d_text(trigger: Trigger<OnAdd, TextSpans2d>, mut commands: Commands) {
+        let entity = trigger.entity();
+        commands
+            .entity(entity)
+            .remove::<TextSpansUi>()
+            .insert(TextSpans);
+        commands.add(move |world: &mut World| {
+            complete_text::<Text2dBundle, TextBundle>(
+                world,
+                entity,
+                &[
+                    take_kept::<Text>,
+                    take_kept::<Anchor>,
+                    take_kept::<Text2dBounds>,
+                    take_kept::<Transform>,
+                    take_kept::<Visibility>,
+                ],
+            );
+        });
+    }
+
+    /// Puts a component taken by [`take_kept`] back.
+    type Restore = Box<dyn FnOnce(&mut EntityWorldMut)>;
+
+    fn take_kept<C: Component>(entity: &mut EntityWorldMut) -> Option<Restore> {
+        let component = entity.take::<C>()?;
+        Some(Box::new(move |entity: &mut EntityWorldMut| {
+            entity.insert(component);
+        }))
+    }
+
+    /// Turns `entity` into a `B` text: removes the components of `Other` that `B` doesn't
+    /// have, then, if any component of `B` is missing, inserts a default `B` while keeping the
+    /// values of the `kept` components the entity already has.
+    ///
+    /// Inserting the whole bundle also covers the components this crate doesn't name.
+    fn complete_text<B: Bundle + Default, Other: Bundle>(
+        world: &mut World,
+        entity: Entity,
+        kept: &[fn(&mut EntityWorldMut) -> Option<Restore>],
+    ) {
+        if !world.entities().contains(entity) {
+            return;
+        }
+        let mut ids = Vec::new();
+        B::get_component_ids(world.components(), &mut |id| ids.push(id));
+        let mut other = Vec::new();
+        Other::get_component_ids(world.components(), &mut |id| other.extend(id));
+        let mut entity = world.entity_mut(entity);
+        for id in other {
+            if !ids.contains(&Some(id)) {
+                entity.remove_by_id(id);
+            }
+        }
+        let complete = ids
+            .iter()
+            .all(|id| id.is_some_and(|id| entity.contains_id(id)));
+        if complete {
+            return;
+        }
+        let restore: Vec<Restore> = kept.iter().filter_map(|take| take(&mut entity)).collect();
+        entity.insert(B::default());
+        for restore in restore {
+            restore(&mut entity);
+        }
+    }
+
+    /// The children
+    ///
+    /// A [`SpanValue`] on the same entity overrides the `value` of the section.
+    #[derive(Component, Debug, Clone, Default, Reflect)]
+    #[reflect(Component, Default)]
+    pub struct TextSpan(pub TextSection);
+
+    /// Links a span (or group) to the `TextSpans` parent (or group) it belongs to,
+    /// instead of being one of its `Children`.
+    ///
+    /// Links to the entity itself or one of its descendants are undone with a warning,
+    /// restoring the previous link if any.
+    ///
+    /// The [`Spans`] of the parent are kept up to date by [`TsePlugin`], so spans can live next
+    /// to ordinary children of a UI node, such as icons, a caret or highlight boxes.
+    /// Links changed in place (e.g. by mapping the entities of a scene) are updated by the next
+    /// sync.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+    #[reflect(Component, MapEntities)]
+    pub struct SpanOf(pub Entity);
+
+    impl Component for SpanOf {
+        const STORAGE_TYPE: StorageType = StorageType::Table;
+
+        fn register_component_hooks(hooks: &mut ComponentHooks) {
+            hooks
+                .on_insert(|world, span, _| link_span(world, span))
+                .on_remove(unlink_span);
+        }
+    }
+
+    impl MapEntities for SpanOf {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.0 = entity_mapper.map_entity(self.0);
+        }
+    }
+
+    /// The spans (and groups) linked to an entity through [`SpanOf`], in the order they were
+    /// linked.
+    ///
+    /// An entity with `Spans` is flattened from these instead of its `Children`.
+    /// Despawning it (or removing this component) despawns its spans. Like `Children`, it is
+    /// removed once its last span is unlinked.
+    #[derive(Debug, Clone, Reflect)]
+    #[reflect(Component, MapEntities)]
+    pub struct Spans(Vec<Entity>);
+
+    impl Component for Spans {
+        const STORAGE_TYPE: StorageType = StorageType::Table;
+
+        fn register_component_hooks(hooks: &mut ComponentHooks) {
+            hooks.on_add(add_pending_spans).on_remove(despawn_spans);
+        }
+    }
+
+    impl Spans {
+        /// The linked entities, in link order
+        pub fn entities(&self) -> &[Entity] {
+            &self.0
+        }
+    }
+
+    impl MapEntities for Spans {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            for span in &mut self.0 {
+                *span = entity_mapper.map_entity(*span);
+            }
+        }
+    }
+
+    /// The bookkeeping behind [`SpanOf`] and [`Spans`].
+    #[derive(Resource, Debug, Default)]
+    pub(crate) struct SpanLinks {
+        /// The entity each [`SpanOf`] was last linked to, since hooks can't see replaced values
+        targets: HashMap<Entity, Entity>,
+        /// Spans linked to entities whose [`Spans`] is being inserted
+        pub(crate) pending: HashMap<Entity, Vec<Entity>>,
+    }
+
+    fn link_span(mut world: DeferredWorld, span: Entity) {
+        let Some(&SpanOf(target)) = world.get::<SpanOf>(span) else {
+            return;
+        };
+        if !world.contains_resource::<SpanLinks>() {
+            return;
+        }
+        if is_linked_below(&world, target, span) {
+            warn!("Ignoring `SpanOf({target:?})` on {span:?}, as it would link it below itself");
+            match world.resource::<SpanLinks>().targets.get(&span) {
+                Some(&previous) => world.commands().entity(span).insert(SpanOf(previous)),
+                None => world.commands().entity(span).remove::<SpanOf>(),
+            };
+            return;
+        }
+        let mut links = world.resource_mut::<SpanLinks>();
+        let previous = links.targets.insert(span, target);
+        if previous == Some(target) {
+            return;
+        }
+        if let Some(previous) = previous {
+            unlink(&mut world, previous, span);
+        }
+        if let Some(mut spans) = world.get_mut::<Spans>(target) {
+            // A `Spans` inserted by a scene may already list it
+            if !spans.0.contains(&span) {
+                spans.0.push(span);
+            }
+            return;
+        }
+        if !world.entities().contains(target) {
+            return;
+        }
+        // Other spans may be linked before the insert is applied
+        let mut links = world.resource_mut::<SpanLinks>();
+        let pending = links.pending.entry(target).or_default();
+        pending.push(span);
+        if pending.len() == 1 {
+            world.commands().add(move |world: &mut World| {
+                if world.entities().contains(target) {
+                    world.entity_mut(target).insert(Spans(Vec::new()));
+                } else {
+                    world.resource_mut::<SpanLinks>().pending.remove(&target);
+                }
+            });
+        }
+    }
+
+    /// Whether `entity` is `span` or below it, through [`SpanOf`] or `Parent`.
+    fn is_linked_below(world: &DeferredWorld, entity: Entity, span: Entity) -> bool {
+        std::iter::successors(Some(entity), |&entity| match world.get::<SpanOf>(entity) {
+            Some(link) => Some(link.0),
+            None => world.get::<Parent>(entity).map(Parent::get),
+        })
+        .any(|entity| entity == span)
+    }
+
+    fn unlink_span(mut world: DeferredWorld, span: Entity, _: ComponentId) {
+        let Some(mut links) = world.get_resource_mut::<SpanLinks>() else {
+            return;
+        };
+        if let Some(target) = links.targets.remove(&span) {
+            unlink(&mut world, target, span);
+        }
+    }
+
+    /// Removes `span` from the [`Spans`] of `target`, or from its pending spans.
+    fn unlink(world: &mut DeferredWorld, target: Entity, span: Entity) {
+        let mut links = world.resource_mut::<SpanLinks>();
+        if let Some(pending) = links.pending.get_mut(&target) {
+            pending.retain(|&entity| entity != span);
+        }
+        let Some(mut spans) = world.get_mut::<Spans>(target) else {
+            return;
+        };
+        spans.0.retain(|&entity| entity != span);
+        if spans.0.is_empty() {
+            world.commands().add(move |world: &mut World| {
+                if let Some(mut target) = world.get_entity_mut(target) {
+                    if target
+                        .get::<Spans>()
+                        .is_some_and(|spans| spans.0.is_empty())
+                    {
+                        target.remove::<Spans>();
+                    }
+                }
+            });
+        }
+    }
+
+    /// Whether a [`SpanOf`] was changed in place since it was linked.
+    fn spans_relinked(
+        links: Res<SpanLinks>,
+        changed: Query<(Entity, &SpanOf), Changed<SpanOf>>,
+    ) -> bool {
+        changed
+            .iter()
+            .any(|(span, link)| links.targets.get(&span) != Some(&link.0))
+    }
+
+    /// Links the spans whose [`SpanOf`] was changed in place, which its hooks don't see,
+    /// e.g. when a scene maps its entities after inserting them.
+    fn relink_spans(
+        world: &mut World,
+        changed: &mut SystemState<Query<(Entity, &SpanOf), Changed<SpanOf>>>,
+    ) {
+        let links = world.resource::<SpanLinks>();
+        let relinked: Vec<Entity> = changed
+            .get(world)
+            .iter()
+            .filter(|(span, link)| links.targets.get(span) != Some(&link.0))
+            .map(|(span, _)| span)
+            .collect();
+        for span in relinked {
+            link_span(world.into(), span);
+        }
+        world.flush();
+    }
+
+    fn add_pending_spans(mut world: DeferredWorld, target: Entity, _: ComponentId) {
+        let Some(mut links) = world.get_resource_mut::<SpanLinks>() else {
+            return;
+        };
+        let pending = links.pending.remove(&target).unwrap_or_default();
+        if let Some(mut spans) = world.get_mut::<Spans>(target) {
+            spans.0.extend(pending);
+        }
+    }
+
+    fn despawn_spans(mut world: DeferredWorld, target: Entity, _: ComponentId) {
+        let spans = world
+            .get::<Spans>(target)
+            .map(|spans| spans.0.clone())
+            .unwrap_or_default();
+        let mut commands = world.commands();
+        for span in spans {
+            commands.entity(span).despawn_recursive();
+        }
+    }
+
+    /// How often a `TextSpans` parent is synced, e.g. to throttle text that changes every frame.
+    ///
+    /// Changes to a throttled parent are coalesced until its next sync, so the last change is
+    /// never dropped. Without this component, parents are synced every frame.
+    #[derive(Component, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+    #[reflect(Component, Default)]
+    pub enum SpanSyncPolicy {
+        /// Synced whenever its spans change
+        #[default]
+        EveryFrame,
+        /// Synced at most this many times per second of [`Time`]
+        MaxRate(f32),
+        /// Only synced while it has a [`SpanSyncRequest`]
+        OnRequest,
+    }
+
+    /// Requests the sync of a [`SpanSyncPolicy::OnRequest`] parent, removed by the next sync.
+    #[derive(Component, Debug, Clone, Default, Reflect)]
+    #[reflect(Component, Default)]
+    pub struct SpanSyncRequest;
+
+    /// A shared value for a span, replacing the `value` of its [`TextSpan`] section.
+    ///
+    /// Cloning an `Arc<str>` doesn't copy the text, so long or repeated values can be shared
+    /// between span entities and edited without reallocating. `Text` owns `String`s, so the sync
+    /// still copies the value into the flattened text, but only when it differs, and into the
+    /// section's existing buffer where possible.
+    ///
+    /// While a span has a `SpanValue`, edits to the `value` of its `TextSpan` are ignored.
+    /// As `Arc<str>` isn't `Reflect`, the whole value is reflected opaquely (and serialized as
+    /// a string), so it survives scenes and reflection clones.
+    #[derive(Component, Debug, Clone, Default, PartialEq, Eq, Reflect)]
+    #[reflect_value(Component, Default, PartialEq, Debug, Serialize, Deserialize)]
+    pub struct SpanValue(pub Arc<str>);
+
+    impl<T: Into<Arc<str>>> From<T> for SpanValue {
+        fn from(value: T) -> Self {
+            Self(value.into())
+        }
+    }
+
+    impl serde::Serialize for SpanValue {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.0)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for SpanValue {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            String::deserialize(deserializer).map(Self::from)
+        }
+    }
+
+    /// A sort key for the sections of a span (or group) among its siblings.
+    ///
+    /// Siblings are stably sorted by this key before flattening, independent of their order in
+    /// `Children`. Siblings without one sort as `0.0`, keeping their `Children` order. To insert
+    /// a span between two others, give it a key between theirs.
+    #[derive(Component, Debug, Clone, Copy, Default, PartialEq, PartialOrd, Reflect)]
+    #[reflect(Component, Default)]
+    pub struct SpanOrder(pub f64);
+
+    /// The `TextSpan` entities of a `TextSpans` parent, in section order.
+    ///
+    /// Written by the sync system, so the section index of a [`TextSection`]
+    /// (e.g. the `metadata` of a cosmic-text hit) can be mapped back to its span entity,
+    /// even when spans are nested or the parent has other children.
+    #[derive(Component, Debug, Clone, Default, Reflect)]
+    #[reflect(Component, Default, MapEntities)]
+    pub struct SpanEntities(Vec<Entity>);
+
+    impl MapEntities for SpanEntities {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            for span in &mut self.0 {
+                *span = entity_mapper.map_entity(*span);
+            }
+        }
+    }
+
+    impl SpanEntities {
+        /// The span entity for the section at `index`
+        pub fn get(&self, index: usize) -> Option<Entity> {
+            self.0.get(index).copied()
+        }
+
+        /// The section index of `span`
+        pub fn index_of(&self, span: Entity) -> Option<usize> {
+            self.0.iter().position(|&entity| entity == span)
+        }
+
+        /// All span entities, in section order
+        pub fn entities(&self) -> &[Entity] {
+            &self.0
+        }
+    }
+
+    /// The span layout of every `TextSpans` parent, as of the last sync.
+    ///
+    /// Shared by the [`TextSink`]s, to patch only the sections whose span changed.
+    #[derive(Resource, Debug, Default)]
+    pub(crate) struct SpanLayouts {
+        /// The span entities of each parent, in section order
+        pub(crate) parents: HashMap<Entity, Vec<Entity>>,
+        /// The parents and section index of each span, one per enclosing `TextSpans` parent
+        pub(crate) spans: HashMap<Entity, Vec<(Entity, usize)>>,
+        /// The parents (or former parents) to walk again on the next sync
+        pub(crate) dirty: HashSet<Entity>,
+        /// The `(parent, index, span)` of sections to patch in place on this sync
+        pub(crate) patches: Vec<(Entity, usize, Entity)>,
+        /// The dirty parents synced into at least one [`TextSink`] on this sync
+        pub(crate) synced: HashSet<Entity>,
+        /// Dirty parents held back by their [`SpanSyncPolicy`], synced once it allows
+        pub(crate) held: HashSet<Entity>,
+        /// The [`Time`] each [`SpanSyncPolicy::MaxRate`] parent was last synced at
+        pub(crate) last_synced: HashMap<Entity, Duration>,
+        /// The change tick each parent's sink was last written by the sync at, so reverse sync
+        /// skips the sync's own writes
+        pub(crate) written: HashMap<Entity, Tick>,
+        /// What the last sync did, for [`TseDiagnosticsPlugin`]
+        pub(crate) stats: SyncStats,
+    }
+
+    /// Per-sync counters, reset by every sync.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub(crate) struct SyncStats {
+        /// The parents walked again, once per sink
+        pub(crate) dirty_parents: usize,
+        /// The sections written by rebuilds and patches
+        pub(crate) rebuilt_sections: usize,
+        /// The sections cloned from spans
+        pub(crate) cloned_sections: usize,
+        /// The bytes of span values copied into sections
+        pub(crate) cloned_bytes: usize,
+        /// The tasks parents were synced in on the `ComputeTaskPool`, 0 if synced sequentially
+        pub(crate) tasks: usize,
+        /// The time spent in `update_parent`, over all sinks
+        pub(crate) time: Duration,
+    }
+
+    impl SpanLayouts {
+        /// Removes the layout of `parent`, returning its previous span entities.
+        fn forget(&mut self, parent: Entity) -> Vec<Entity> {
+            let spans = self.parents.remove(&parent).unwrap_or_default();
+            for span in &spans {
+                if let Some(owners) = self.spans.get_mut(span) {
+                    owners.retain(|&(owner, _)| owner != parent);
+                    if owners.is_empty() {
+                        self.spans.remove(span);
+                    }
+                }
+            }
+            spans
+        }
+
+        /// Marks the parents `entity` was last synced into, and the parents it is below now.
+        fn mark(&mut self, entity: Entity, hierarchy: &SpanHierarchy) {
+            if let Some(owners) = self.spans.get(&entity) {
+                self.dirty.extend(owners.iter().map(|&(parent, _)| parent));
+            }
+            self.dirty.extend(hierarchy.find_roots(entity));
+        }
+    }
+
+    /// A partial style on a `TextSpans` parent, a group entity between the parent and its spans,
+    /// or a span itself.
+    ///
+    /// Each field set here overrides the `TextSpan` style of every span at or below this entity,
+    /// with the nearest `SpanStyle` winning, like a CSS cascade. Fields left as `None` are
+    /// inherited from further up, falling back to the span's own `TextStyle`.
+    ///
+    /// Entities with a `SpanStyle` but no `TextSpan` act as groups: they contribute no section
+    /// of their own, but their children are flattened in place.
+    #[derive(Component, Debug, Clone, Default, Reflect)]
+    #[reflect(Component, Default)]
+    pub struct SpanStyle {
+        pub font: Option<Handle<Font>>,
+        pub font_size: Option<f32>,
+        pub color: Option<Color>,
+    }
+
+    impl SpanStyle {
+        /// Fills the fields not set on `self` from `ancestor`.
+        fn inherit(&mut self, ancestor: &SpanStyle) {
+            if self.font.is_none() {
+                self.font.clone_from(&ancestor.font);
+            }
+            if self.font_size.is_none() {
+                self.font_size = ancestor.font_size;
+            }
+            if self.color.is_none() {
+                self.color = ancestor.color;
+            }
+        }
+
+        /// Overrides the fields of `style` that are set on `self`.
+        fn apply(&self, style: &mut TextStyle) {
+            if let Some(font) = &self.font {
+                style.font.clone_from(font);
+            }
+            if let Some(font_size) = self.font_size {
+                style.font_size = font_size;
+            }
+            if let Some(color) = self.color {
+                style.color = color;
+            }
+        }
+    }
+
+    /// How spans hidden through [`Visibility`] are synced to the `Text` of a `TextSpans` parent.
+    ///
+    /// A span is hidden when it, or a group between it and the parent, is
+    /// [`Visibility::Hidden`] (the nearest non-[`Visibility::Inherited`] value wins, like
+    /// [`InheritedVisibility`]). Without this component, hidden spans are omitted.
+    #[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+    #[reflect(Component, Default)]
+    pub enum SpanVisibilityMode {
+        /// Hidden spans have no section, so the surrounding text reflows
+        #[default]
+        Omit,
+        /// Hidden spans keep their section but are fully transparent, so the layout is unchanged
+        Preserve,
+    }
+
+    /// Read-only access to the span hierarchies below `TextSpans` parents.
+    #[allow(clippy::type_complexity)]
+    #[derive(SystemParam)]
+    struct SpanHierarchy<'w, 's> {
+        roots: Query<
+            'w,
+            's,
+            Option<&'static SpanVisibilityMode>,
+            (With<TextSpans>, Without<TextSpan>),
+        >,
+        spans: Query<
+            'w,
+            's,
+            (&'static TextSpan, Option<&'static SpanValue>),
+            Or<(With<Parent>, With<SpanOf>)>,
+        >,
+        styles: Query<'w, 's, &'static SpanStyle>,
+        visibility: Query<'w, 's, &'static Visibility>,
+        decorations: Query<'w, 's, (), With<SpanDecoration>>,
+        orders: Query<'w, 's, &'static SpanOrder>,
+        children: Query<'w, 's, &'static Children>,
+        ancestors: Query<'w, 's, &'static Parent>,
+        links: Query<'w, 's, &'static SpanOf>,
+        linked: Query<'w, 's, &'static Spans>,
+    }
+
+    impl SpanHierarchy<'_, '_> {
+        /// The value and own style of the span `entity`, preferring its [`SpanValue`].
+        fn span(&self, entity: Entity) -> Option<(&str, &TextStyle)> {
+            let (span, value) = self.spans.get(entity).ok()?;
+            let text = value.map_or(span.0.value.as_str(), |value| &value.0);
+            Some((text, &span.0.style))
+        }
+
+        /// The `TextSpans` parents at or above `entity`, nearest first.
+        ///
+        /// A `TextSpans` parent embedded below another is flattened into it, so a span is synced
+        /// into every enclosing parent.
+        ///
+        /// Stops at a cycle, e.g. a [`SpanOf`] to itself that [`TsePlugin`] hasn't removed yet.
+        fn find_roots(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+            let mut visited = HashSet::new();
+            std::iter::successors(Some(entity), |&entity| self.parent_of(entity))
+                .take_while(move |&entity| visited.insert(entity))
+                .filter(|&entity| self.roots.contains(entity))
+        }
+
+        /// The entity `entity` is a span (or group) of, through [`SpanOf`] or else `Parent`.
+        fn parent_of(&self, entity: Entity) -> Option<Entity> {
+            match self.links.get(entity) {
+                Ok(link) => Some(link.0),
+                Err(_) => self.ancestors.get(entity).ok().map(Parent::get),
+            }
+        }
+
+        /// Whether the `TextSpans` parent `root` is embedded below another one.
+        fn is_embedded(&self, root: Entity) -> bool {
+            self.parent_of(root)
+                .is_some_and(|parent| self.find_roots(parent).next().is_some())
+        }
+
+        /// The [`Spans`] of `entity`, or else its children, stably sorted by [`SpanOrder`]
+        /// if any of them has one.
+        fn ordered_children(&self, entity: Entity) -> Vec<Entity> {
+            let mut children = match self.linked.get(entity) {
+                Ok(spans) => spans.0.clone(),
+                Err(_) => self
+                    .children
+                    .get(entity)
+                    .map(|c| c.to_vec())
+                    .unwrap_or_default(),
+            };
+            if children.iter().any(|&child| self.orders.contains(child)) {
+                let order = |child: &Entity| self.orders.get(*child).map_or(0.0, |order| order.0);
+                children.sort_by(|a, b| order(a).total_cmp(&order(b)));
+            }
+            children
+        }
+
+        /// The spans, groups and embedded `TextSpans` parents directly below `entity`,
+        /// in section order.
+        fn nested_spans(&self, entity: Entity) -> Vec<Entity> {
+            self.ordered_children(entity)
+                .into_iter()
+                .filter(|&child| {
+                    self.spans.contains(child)
+                        || self.styles.contains(child)
+                        || self.roots.contains(child)
+                })
+                .collect()
+        }
+
+        /// All descendants of `entity` in document order, so nested spans are flattened in place.
+        ///
+        /// [`SpanDecoration`]s and their descendants are skipped.
+        fn descendants_depth_first(&self, entity: Entity) -> Vec<Entity> {
+            let mut descendants = Vec::new();
+            let mut stack = self.ordered_children(entity);
+            stack.reverse();
+            while let Some(entity) = stack.pop() {
+                if self.decorations.contains(entity) {
+                    continue;
+                }
+                descendants.push(entity);
+                stack.extend(self.ordered_children(entity).into_iter().rev());
+            }
+            descendants
+        }
+
+        /// The spans of `root` in section order, like its [`SpanEntities`] after a sync.
+        fn section_spans(&self, root: Entity) -> Vec<Entity> {
+            let mode = self.visibility_mode(root);
+            self.descendants_depth_first(root)
+                .into_iter()
+                .filter(|&child| self.spans.contains(child))
+                .filter(|&child| {
+                    mode == SpanVisibilityMode::Preserve || !self.is_hidden(child, root)
+                })
+                .collect()
+        }
+
+        /// `span` and its ancestors, up to and including `root`.
+        fn path_to_root(&self, span: Entity, root: Entity) -> impl Iterator<Item = Entity> + '_ {
+            std::iter::successors(Some(span), move |&entity| {
+                (entity != root).then(|| self.parent_of(entity)).flatten()
+            })
+        }
+
+        fn visibility_mode(&self, root: Entity) -> SpanVisibilityMode {
+            self.roots
+                .get(root)
+                .ok()
+                .flatten()
+                .copied()
+                .unwrap_or_default()
+        }
+
+        /// Whether `span` is hidden by its own or a group's [`Visibility`] below `root`.
+        fn is_hidden(&self, span: Entity, root: Entity) -> bool {
+            self.path_to_root(span, root)
+                .filter(|&entity| entity != root)
+                .filter_map(|entity| self.visibility.get(entity).ok())
+                .find(|&&visibility| visibility != Visibility::Inherited)
+                .is_some_and(|&visibility| visibility == Visibility::Hidden)
+        }
+
+        /// The style of `span` after cascading every `SpanStyle` from `root` down to it.
+        fn resolve_style(&self, span: Entity, root: Entity, style: &TextStyle) -> TextStyle {
+            let mut cascade = SpanStyle::default();
+            for entity in self.path_to_root(span, root) {
+                if let Ok(span_style) = self.styles.get(entity) {
+                    cascade.inherit(span_style);
+                }
+            }
+            let mut style = style.clone();
+            cascade.apply(&mut style);
+            style
+        }
+
+        /// The style `span` is synced with: its cascaded style, transparent if it is hidden.
+        fn synced_style(&self, span: Entity, root: Entity, style: &TextStyle) -> TextStyle {
+            let mut style = self.resolve_style(span, root, style);
+            if self.is_hidden(span, root) {
+                style.color.set_alpha(0.0);
+            }
+            style
+        }
+    }
+
+    /// `TextStyle` doesn't implement `PartialEq`.
+    fn same_style(a: &TextStyle, b: &TextStyle) -> bool {
+        a.font == b.font && a.font_size == b.font_size && a.color == b.color
+    }
+
+    /// In-place edits that invalidate sections, found through change detection by every backend.
+    #[allow(clippy::type_complexity)]
+    #[derive(SystemParam)]
+    struct SpanEdits<'w, 's> {
+        spans: Query<'w, 's, Entity, Or<(Changed<TextSpan>, Changed<SpanValue>)>>,
+        styles: Query<'w, 's, Entity, Changed<SpanStyle>>,
+        visibility: Query<'w, 's, (Entity, Has<TextSpans>), Changed<Visibility>>,
+        modes: Query<'w, 's, Entity, Changed<SpanVisibilityMode>>,
+        orders: Query<'w, 's, Entity, Changed<SpanOrder>>,
+        moved: Query<
+            'w,
+            's,
+            Entity,
+            (
+                Or<(With<TextSpan>, With<SpanStyle>, With<TextSpans>)>,
+                Or<(Changed<Parent>, Changed<SpanOf>)>,
+            ),
+        >,
+    }
+
+    impl SpanEdits<'_, '_> {
+        /// Marks the parents that must be walked again, and returns the `(parent, index, span)`
+        /// of sections that only need patching in place.
+        fn collect(
+            &self,
+            layouts: &mut SpanLayouts,
+            hierarchy: &SpanHierarchy,
+        ) -> Vec<(Entity, usize, Entity)> {
+            // Style, visibility and order changes affect every span below them
+            for entity in self.styles.iter().chain(&self.modes).chain(&self.orders) {
+                layouts.dirty.extend(hierarchy.find_roots(entity));
+            }
+            // The visibility of a `TextSpans` parent doesn't affect its own text, only the texts
+            // it is embedded in
+            for (entity, is_root) in &self.visibility {
+                let entity = match is_root {
+                    true => hierarchy.parent_of(entity),
+                    false => Some(entity),
+                };
+                if let Some(entity) = entity {
+                    layouts.dirty.extend(hierarchy.find_roots(entity));
+                }
+            }
+            // Reparented spans and groups leave their previous parent and join a new one.
+            // `Parent` is updated in place when reparenting, so this can't be observed.
+            for entity in &self.moved {
+                layouts.mark(entity, hierarchy);
+                if !layouts.spans.contains_key(&entity) {
+                    let previous = hierarchy
+                        .descendants_depth_first(entity)
+                        .into_iter()
+                        .find_map(|span| layouts.spans.get(&span))
+                        .cloned()
+                        .unwrap_or_default();
+                    layouts
+                        .dirty
+                        .extend(previous.into_iter().map(|(parent, _)| parent));
+                }
+            }
+            let mut patch = Vec::new();
+            for child in &self.spans {
+                match layouts.spans.get(&child) {
+                    Some(owners) => {
+                        patch.extend(owners.iter().map(|&(parent, index)| (parent, index, child)));
+                    }
+                    None => layouts.mark(child, hierarchy),
+                }
+            }
+            patch.retain(|(parent, _, _)| !layouts.dirty.contains(parent));
+            patch
+        }
+    }
+
+    /// Structural changes that invalidate sections, polled by [`SyncBackend::Polling`].
+    #[allow(clippy::type_complexity)]
+    #[derive(SystemParam)]
+    struct SpanStructure<'w, 's> {
+        children:
+            Query<'w, 's, Entity, (Without<TextSpan>, Or<(Changed<Children>, Changed<Spans>)>)>,
+        removed_roots: RemovedComponents<'w, 's, TextSpans>,
+        removed_spans: RemovedComponents<'w, 's, TextSpan>,
+        removed_styles: RemovedComponents<'w, 's, SpanStyle>,
+        removed_visibility: RemovedComponents<'w, 's, Visibility>,
+        removed_modes: RemovedComponents<'w, 's, SpanVisibilityMode>,
+        removed_orders: RemovedComponents<'w, 's, SpanOrder>,
+        removed_values: RemovedComponents<'w, 's, SpanValue>,
+        removed_children: RemovedComponents<'w, 's, Children>,
+        removed_parents: RemovedComponents<'w, 's, Parent>,
+        removed_links: RemovedComponents<'w, 's, SpanOf>,
+        removed_linked: RemovedComponents<'w, 's, Spans>,
+    }
+
+    /// Marks parents whose span structure changed since the last sync.
+    fn poll_structure(
+        mut layouts: ResMut<SpanLayouts>,
+        hierarchy: SpanHierarchy,
+        mut structure: SpanStructure,
+    ) {
+        // Parents and groups whose children changed, or whose last child went away
+        for entity in structure
+            .children
+            .iter()
+            .chain(structure.removed_children.read())
+            .chain(structure.removed_linked.read())
+        {
+            layouts.dirty.extend(hierarchy.find_roots(entity));
+        }
+        // Removed parents are forgotten by the sync.
+        // Spans that lost `TextSpan` or their `Parent` (including despawned ones) are found
+        // through the layout, since they may no longer be connected to their old parent.
+        for entity in structure
+            .removed_roots
+            .read()
+            .chain(structure.removed_spans.read())
+            .chain(structure.removed_styles.read())
+            .chain(structure.removed_visibility.read())
+            .chain(structure.removed_modes.read())
+            .chain(structure.removed_orders.read())
+            .chain(structure.removed_values.read())
+            .chain(structure.removed_parents.read())
+            .chain(structure.removed_links.read())
+        {
+            layouts.mark(entity, &hierarchy);
+        }
+    }
+
+    /// Marks the parent of an entity whose span-related component `C` was inserted or removed,
+    /// used by [`SyncBackend::Observers`].
+    ///
+    /// `Parent` and `Visibility` are on most entities, so entities that aren't spans, groups or
+    /// `TextSpans` parents (or weren't synced as spans) return early.
+    #[allow(clippy::type_complexity)]
+    fn observe_structure<E, C: Component>(
+        trigger: Trigger<E, C>,
+        mut layouts: ResMut<SpanLayouts>,
+        hierarchy: SpanHierarchy,
+        relevant: Query<(), Or<(With<TextSpan>, With<SpanStyle>, With<TextSpans>)>>,
+    ) {
+        let entity = trigger.entity();
+        if !relevant.contains(entity) && !layouts.spans.contains_key(&entity) {
+            return;
+        }
+        layouts.mark(entity, &hierarchy);
+    }
+
+    /// Propagates direct edits of the sink `T` of a `TextSpans` parent back to its spans,
+    /// updating, spawning or despawning `TextSpan` children to match the sections.
+    ///
+    /// Parents with pending span changes are skipped, the spans win.
+    #[allow(clippy::type_complexity)]
+    fn reverse_sync<T: TextSink>(
+        mut commands: Commands,
+        layouts: Res<SpanLayouts>,
+        hierarchy: SpanHierarchy,
+        edited: Query<(Entity, Ref<T>), (With<TextSpans>, Without<TextSpan>, Changed<T>)>,
+        changed_spans: Query<(), Or<(Changed<TextSpan>, Changed<SpanValue>)>>,
+        values: Query<(), With<SpanValue>>,
+    ) {
+        for (parent, text) in &edited {
+            // Last changed by the sync itself, nothing to propagate
+            if layouts.written.get(&parent) == Some(&text.last_changed()) {
+                continue;
+            }
+            if layouts.dirty.contains(&parent) || layouts.held.contains(&parent) {
+                continue;
+            }
+            let spans = layouts
+                .parents
+                .get(&parent)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            if spans.iter().any(|&span| changed_spans.contains(span)) {
+                continue;
+            }
+            for (index, section) in text.sections().iter().enumerate() {
+                let Some(&span) = spans.get(index) else {
+                    let span = TextSpan(section.clone());
+                    if hierarchy.linked.contains(parent) {
+                        commands.spawn((span, SpanOf(parent)));
+                    } else {
+                        commands.spawn(span).set_parent(parent);
+                    }
+                    continue;
+                };
+                let (Ok((current, _)), Some((value, style))) =
+                    (hierarchy.spans.get(span), hierarchy.span(span))
+                else {
+                    continue;
+                };
+                // Only write the style back if it was edited, since the synced style may be
+                // cascaded or made transparent
+                let synced = hierarchy.synced_style(span, parent, style);
+                let style_edited = !same_style(&section.style, &synced);
+                let value_edited = section.value != value;
+                if values.contains(span) {
+                    if value_edited {
+                        commands
+                            .entity(span)
+                            .insert(SpanValue::from(section.value.as_str()));
+                    }
+                    if style_edited {
+                        commands.entity(span).insert(TextSpan(TextSection {
+                            value: current.0.value.clone(),
+                            style: section.style.clone(),
+                        }));
+                    }
+                } else if value_edited || style_edited {
+                    let style = if style_edited { &section.style } else { style };
+                    commands.entity(span).insert(TextSpan(TextSection {
+                        value: section.value.clone(),
+                        style: style.clone(),
+                    }));
+                }
+            }
+            for &span in spans.iter().skip(text.sections().len()) {
+                commands.entity(span).despawn_recursive();
+            }
         }
     }
 
-    /// The parent
-    #[derive(Component, Debug, Clone, Default, Reflect)]
-    #[reflect(Component, Default)]
-    pub struct TextSpans;
+    /// Sends `error` and handles it according to the [`SyncErrorPolicy`].
+    fn report(
+        error: SpanSyncError,
+        policy: SyncErrorPolicy,
+        errors: &mut EventWriter<SpanSyncError>,
+        commands: &mut Commands,
+    ) {
+        errors.send(error);
+        match policy {
+            SyncErrorPolicy::Log => error!("{error}"),
+            SyncErrorPolicy::Ignore => {}
+            SyncErrorPolicy::PanicInDebug => {
+                if cfg!(debug_assertions) {
+                    panic!("{error}");
+                }
+                error!("{error}");
+            }
+            SyncErrorPolicy::Repair => match error.kind {
+                SpanSyncErrorKind::MissingText => {
+                    commands.entity(error.parent).insert(Text::default());
+                }
+                SpanSyncErrorKind::MissingTextSpan { child } => {
+                    commands.entity(child).insert(SpanDecoration);
+                }
+            },
+        }
+    }
 
-    /// The children
-    #[derive(Component, Debug, Clone, Default, Reflect)]
-    #[reflect(Component, Default)]
-    pub struct TextSpan(pub TextSection);
+    /// The outcome of syncing one parent, applied to the [`SpanLayouts`] after the parallel sync.
+    struct Synced {
+        parent: Entity,
+        /// The span entities in section order, if the parent was walked again
+        spans: Option<Vec<Entity>>,
+        /// Whether the parent still needs a [`SpanEntities`]
+        insert_span_entities: bool,
+        /// Descendants that are neither spans, groups nor decorations
+        missing_spans: Vec<Entity>,
+        cloned: usize,
+        cloned_bytes: usize,
+        /// The number of sections written
+        written: usize,
+        /// Whether the sink was changed
+        sink_changed: bool,
+    }
+
+    /// Walks `parent` again, reusing the sections of spans whose value is unchanged.
+    ///
+    /// `text` is only borrowed mutably if its sections differ, to not trigger text layout.
+    fn rebuild_parent<T: TextSink>(
+        parent: Entity,
+        text: &mut Mut<T>,
+        span_entities: Option<Mut<SpanEntities>>,
+        old_spans: &[Entity],
+        hierarchy: &SpanHierarchy,
+    ) -> Synced {
+        let mode = hierarchy.visibility_mode(parent);
+        let old_indices: HashMap<Entity, usize> = old_spans
+            .iter()
+            .enumerate()
+            .map(|(index, &span)| (span, index))
+            .collect();
+
+        // Each span value, the index of its previous section if the value is the same,
+        // and its style
+        let mut sections = Vec::new();
+        let mut missing_spans = Vec::new();
+        for child in hierarchy.descendants_depth_first(parent) {
+            let Some((value, style)) = hierarchy.span(child) else {
+                if !hierarchy.styles.contains(child) && !hierarchy.roots.contains(child) {
+                    missing_spans.push(child);
+                }
+                continue;
+            };
+            if mode == SpanVisibilityMode::Omit && hierarchy.is_hidden(child, parent) {
+                continue;
+            }
+            let reused = old_indices.get(&child).copied().filter(|&index| {
+                text.sections()
+                    .get(index)
+                    .is_some_and(|section| section.value == value)
+            });
+            let style = hierarchy.synced_style(child, parent, style);
+            sections.push((value, reused, style));
+        }
+
+        let unchanged = sections.len() == text.sections().len()
+            && sections
+                .iter()
+                .zip(text.sections())
+                .all(|((value, _, style), section)| {
+                    section.value == *value && same_style(style, &section.style)
+                });
+        let mut cloned = 0;
+        let mut cloned_bytes = 0;
+        let mut written = 0;
+        if !unchanged {
+            written = sections.len();
+            // Reuse the previous value if it is the same, otherwise copy it.
+            // The style is cheap to clone and is always re-resolved.
+            let mut old_sections = std::mem::take(text.sections_mut());
+            *text.sections_mut() = sections
+                .into_iter()
+                .map(|(value, reused, style)| {
+                    let value = match reused {
+                        Some(index) => std::mem::take(&mut old_sections[index].value),
+                        None => {
+                            cloned += 1;
+                            cloned_bytes += value.len();
+                            value.to_owned()
+                        }
+                    };
+                    TextSection { value, style }
+                })
+                .collect();
+        }
+
+        let spans = hierarchy.section_spans(parent);
+        let insert_span_entities = match span_entities {
+            Some(mut span_entities) => {
+                if span_entities.0 != spans {
+                    span_entities.0.clone_from(&spans);
+                }
+                false
+            }
+            None => true,
+        };
+        Synced {
+            parent,
+            spans: Some(spans),
+            insert_span_entities,
+            missing_spans,
+            cloned,
+            cloned_bytes,
+            written,
+            sink_changed: false,
+        }
+    }
+
+    /// Replaces the sections of the changed spans of `parent`, given as `(index, span)`.
+    ///
+    /// Sections that are already up to date are left alone, to not trigger text layout.
+    /// Otherwise the value is copied into the section's existing buffer.
+    fn patch_parent<T: TextSink>(
+        parent: Entity,
+        text: &mut Mut<T>,
+        patches: &[(usize, Entity)],
+        hierarchy: &SpanHierarchy,
+    ) -> Synced {
+        let mut cloned_bytes = 0;
+        let mut written = 0;
+        for &(index, child) in patches {
+            let (Some(section), Some((value, style))) =
+                (text.sections().get(index), hierarchy.span(child))
+            else {
+                continue;
+            };
+            let style = hierarchy.synced_style(child, parent, style);
+            if section.value == value && same_style(&style, &section.style) {
+                continue;
+            }
+            let section = &mut text.sections_mut()[index];
+            section.value.clear();
+            section.value.push_str(value);
+            section.style = style;
+            cloned_bytes += value.len();
+            written += 1;
+        }
+        Synced {
+            parent,
+            spans: None,
+            insert_span_entities: false,
+            missing_spans: Vec::new(),
+            cloned: written,
+            cloned_bytes,
+            written,
+            sink_changed: false,
+        }
+    }
+
+    /// Collects the edits since the last sync into the [`SpanLayouts`], for every [`TextSink`].
+    ///
+    /// Parents held back by their [`SpanSyncPolicy`] keep their changes for a later sync.
+    fn collect_edits(
+        mut commands: Commands,
+        mut layouts: ResMut<SpanLayouts>,
+        hierarchy: SpanHierarchy,
+        edits: SpanEdits,
+        time: Option<Res<Time>>,
+        policies: Query<(&SpanSyncPolicy, Has<SpanSyncRequest>)>,
+        requests: Query<Entity, With<SpanSyncRequest>>,
+    ) {
+        let layouts = &mut *layouts;
+        layouts.stats = SyncStats::default();
+        let held = std::mem::take(&mut layouts.held);
+        layouts.dirty.extend(held);
+        // Parents whose span structure, cascaded styles or visibility changed must be walked
+        // again, everything else only needs the changed sections patched in place.
+        layouts.patches = edits.collect(layouts, &hierarchy);
+
+        let now = time.map(|time| time.elapsed());
+        let mut pending: HashSet<Entity> = layouts.dirty.iter().copied().collect();
+        pending.extend(layouts.patches.iter().map(|&(parent, _, _)| parent));
+        for parent in pending {
+            let Ok((policy, requested)) = policies.get(parent) else {
+                continue;
+            };
+            let allowed = match policy {
+                SpanSyncPolicy::EveryFrame => true,
+                SpanSyncPolicy::MaxRate(rate) => match (now, layouts.last_synced.get(&parent)) {
+                    (Some(now), Some(last)) => {
+                        // `Time` may lag behind, e.g. when flushed from `FixedUpdate`
+                        now.saturating_sub(*last).as_secs_f32() * rate >= 1.0
+                    }
+                    _ => true,
+                },
+                SpanSyncPolicy::OnRequest => requested,
+            };
+            if allowed {
+                if let (SpanSyncPolicy::MaxRate(_), Some(now)) = (policy, now) {
+                    layouts.last_synced.insert(parent, now);
+                }
+            } else {
+                // Patches are lost with their change detection, so walk the parent again later
+                layouts.dirty.remove(&parent);
+                layouts.held.insert(parent);
+            }
+        }
+        let held = &layouts.held;
+        layouts
+            .patches
+            .retain(|(parent, _, _)| !held.contains(parent));
+        for entity in &requests {
+            commands.entity(entity).remove::<SpanSyncRequest>();
+        }
+    }
 
+    /// Marks the parents a sink `T` was inserted on, since it starts empty and can't be patched.
     #[allow(clippy::type_complexity)]
-    fn update_parent(
-        mut changed: Local<std::collections::HashSet<Entity>>,
-        changed_parents: Query<Entity, (With<TextSpans>, Changed<Children>, Without<TextSpan>)>,
-        mut parents: Query<&mut Text, (With<TextSpans>, With<Children>, Without<TextSpan>)>,
-        changed_children: Query<&Parent, Changed<TextSpan>>,
-        all_children: Query<&TextSpan, With<Parent>>,
-        children: Query<&Children>,
+    fn mark_added_sinks<T: TextSink>(
+        mut layouts: ResMut<SpanLayouts>,
+        added: Query<Entity, (Added<T>, With<TextSpans>, Without<TextSpan>)>,
+    ) {
+        let layouts = &mut *layouts;
+        let added = added.iter().filter(|parent| !layouts.held.contains(parent));
+        layouts.dirty.extend(added);
+    }
+
+    /// Syncs every changed `TextSpans` parent with a sink `T`, in parallel.
+    #[allow(clippy::type_complexity, clippy::too_many_arguments)]
+    fn update_parent<T: TextSink>(
+        mut commands: Commands,
+        mut layouts: ResMut<SpanLayouts>,
+        policy: Res<SyncErrorPolicy>,
+        mut errors: EventWriter<SpanSyncError>,
+        hierarchy: SpanHierarchy,
+        mut parents: Query<
+            (Entity, &mut T, Option<&mut SpanEntities>),
+            (With<TextSpans>, Without<TextSpan>),
+        >,
+        ticks: SystemChangeTick,
     ) {
-        for parent in &changed_children {
-            changed.insert(parent.get());
+        let start = Instant::now();
+        let this_run = ticks.this_run();
+        let layouts = &mut *layouts;
+        let rebuild: HashSet<Entity> = layouts
+            .dirty
+            .iter()
+            .copied()
+            .filter(|&parent| hierarchy.roots.contains(parent) && parents.contains(parent))
+            .collect();
+        layouts.synced.extend(&rebuild);
+        let mut patches: HashMap<Entity, Vec<(usize, Entity)>> = HashMap::new();
+        for &(parent, index, child) in &layouts.patches {
+            if parents.contains(parent) {
+                patches.entry(parent).or_default().push((index, child));
+            }
         }
-        for parent in &changed_parents {
-            changed.insert(parent);
+        if rebuild.is_empty() && patches.is_empty() {
+            return;
         }
+        layouts.stats.dirty_parents += rebuild.len();
 
-        for parent in changed.drain() {
-            if let Ok(mut text) = parents.get_mut(parent) {
-                text.sections.clear();
-                for child in children.iter_descendants(parent) {
-                    if let Ok(span) = all_children.get(child) {
-                        text.sections.push(span.0.clone());
-                    } else {
-                        error!("Missing `TextSpan` for child {child:?} for parent {parent:?}");
+        // Only the dirty and patched parents are visited. Each one only touches its own sink,
+        // so they can be synced in parallel. The shared layouts are updated afterwards, in
+        // entity order to stay deterministic.
+        let targets: Vec<Entity> = rebuild
+            .iter()
+            .chain(patches.keys().filter(|parent| !rebuild.contains(parent)))
+            .copied()
+            .collect();
+        let old_layouts = &*layouts;
+        let sync = |parent, mut text: Mut<'_, T>, span_entities: Option<Mut<'_, _>>| {
+            let mut synced = if rebuild.contains(&parent) {
+                let old_spans = old_layouts
+                    .parents
+                    .get(&parent)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                rebuild_parent(parent, &mut text, span_entities, old_spans, &hierarchy)
+            } else {
+                patch_parent(parent, &mut text, &patches[&parent], &hierarchy)
+            };
+            synced.sink_changed = text.last_changed() == this_run;
+            synced
+        };
+        let mut tasks = 0;
+        let mut results: Vec<Synced> = match ComputeTaskPool::try_get() {
+            Some(pool) if targets.len() > 1 => {
+                let (parents, sync) = (&parents, &sync);
+                let chunk_size = targets.len().div_ceil(pool.thread_num().max(1));
+                tasks = targets.len().div_ceil(chunk_size);
+                pool.scope(|scope| {
+                    for chunk in targets.chunks(chunk_size) {
+                        scope.spawn(async move {
+                            let mut results = Vec::with_capacity(chunk.len());
+                            for &parent in chunk {
+                                // SAFETY: `targets` holds each parent once, so no two tasks
+                                // access the same components, and `parents` is only borrowed
+                                // shared until the scope ends.
+                                let item = unsafe { parents.get_unchecked(parent) };
+                                if let Ok((parent, text, span_entities)) = item {
+                                    results.push(sync(parent, text, span_entities));
+                                }
+                            }
+                            results
+                        });
                     }
+                })
+                .into_iter()
+                .flatten()
+                .collect()
+            }
+            // Without a `TaskPoolPlugin`, or nothing to split
+            _ => targets
+                .iter()
+                .filter_map(|&parent| {
+                    let (parent, text, span_entities) = parents.get_mut(parent).ok()?;
+                    Some(sync(parent, text, span_entities))
+                })
+                .collect(),
+        };
+        results.sort_unstable_by_key(|synced| synced.parent);
+        layouts.stats.tasks += tasks;
+
+        for synced in results {
+            let parent = synced.parent;
+            layouts.stats.cloned_sections += synced.cloned;
+            layouts.stats.cloned_bytes += synced.cloned_bytes;
+            layouts.stats.rebuilt_sections += synced.written;
+            if synced.sink_changed {
+                layouts.written.insert(parent, this_run);
+            }
+            for child in synced.missing_spans {
+                let error = SpanSyncError {
+                    parent,
+                    kind: SpanSyncErrorKind::MissingTextSpan { child },
+                };
+                report(error, *policy, &mut errors, &mut commands);
+            }
+            let Some(spans) = synced.spans else {
+                continue;
+            };
+            layouts.forget(parent);
+            for (index, &span) in spans.iter().enumerate() {
+                layouts.spans.entry(span).or_default().push((parent, index));
+            }
+            if synced.insert_span_entities {
+                commands.entity(parent).insert(SpanEntities(spans.clone()));
+            }
+            layouts.parents.insert(parent, spans);
+        }
+        layouts.stats.time += start.elapsed();
+    }
+
+    /// Forgets the dirty parents that are no longer `TextSpans`,
+    /// and reports those that no [`TextSink`] synced.
+    fn finish_sync(
+        mut commands: Commands,
+        mut layouts: ResMut<SpanLayouts>,
+        policy: Res<SyncErrorPolicy>,
+        mut errors: EventWriter<SpanSyncError>,
+        hierarchy: SpanHierarchy,
+    ) {
+        let layouts = &mut *layouts;
+        layouts.patches.clear();
+        let synced = std::mem::take(&mut layouts.synced);
+        let mut dirty: Vec<Entity> = std::mem::take(&mut layouts.dirty)
+            .into_iter()
+            .filter(|parent| !synced.contains(parent))
+            .collect();
+        dirty.sort_unstable();
+        for parent in dirty {
+            if !hierarchy.roots.contains(parent) {
+                // No longer `TextSpans`, or despawned
+                layouts.forget(parent);
+                layouts.last_synced.remove(&parent);
+                layouts.written.remove(&parent);
+                continue;
+            }
+            if hierarchy.is_embedded(parent) {
+                // Flattened into its enclosing parents instead
+                continue;
+            }
+            let error = SpanSyncError {
+                parent,
+                kind: SpanSyncErrorKind::MissingText,
+            };
+            report(error, *policy, &mut errors, &mut commands);
+            if *policy == SyncErrorPolicy::Repair {
+                // Sync into the inserted `Text` next time
+                layouts.dirty.insert(parent);
+            }
+        }
+    }
+
+    /// Edits the spans of a `TextSpans` parent by the index of their section, like in its
+    /// [`SpanEntities`].
+    ///
+    /// Indices are resolved when the commands are applied, so they see the edits queued before.
+    /// New spans are linked through [`SpanOf`], unless the parent already has span children.
+    /// They are despawned again if the entity isn't a `TextSpans` parent by then.
+    pub trait SpanCommandsExt {
+        /// Adds `span` after the last section, returning its entity.
+        fn append_span(&mut self, span: TextSpan) -> Entity;
+
+        /// Adds `span` so it becomes the section at `index`, returning its entity.
+        ///
+        /// It is placed next to the span currently at `index`, so it inherits the same styles.
+        fn insert_span_at(&mut self, index: usize, span: TextSpan) -> Entity;
+
+        /// Despawns the span of the section at `index`. Spans nested below it take its place.
+        ///
+        /// The span isn't returned, as it is only known once the index is resolved, see
+        /// [`SpanWorldExt::remove_span_at`] for that.
+        fn remove_span_at(&mut self, index: usize) -> &mut Self;
+
+        /// Despawns the span of the section at `index` and puts `span` in its place,
+        /// returning its entity.
+        fn replace_span(&mut self, index: usize, span: TextSpan) -> Entity;
+
+        /// Despawns every span, group and embedded `TextSpans` parent, keeping other children.
+        ///
+        /// The despawned entities aren't returned, see [`SpanWorldExt::clear_spans`] for that.
+        fn clear_spans(&mut self) -> &mut Self;
+
+        /// Replaces every span with a single one, styled like the first section,
+        /// returning its entity.
+        fn set_plain_text(&mut self, text: impl Into<String>) -> Entity;
+    }
+
+    impl SpanCommandsExt for EntityCommands<'_> {
+        fn append_span(&mut self, span: TextSpan) -> Entity {
+            self.insert_span_at(usize::MAX, span)
+        }
+
+        fn insert_span_at(&mut self, index: usize, span: TextSpan) -> Entity {
+            let root = self.id();
+            let span = self.commands().spawn(span).id();
+            self.commands().add(move |world: &mut World| {
+                insert_span(world, root, index, span);
+            });
+            span
+        }
+
+        fn remove_span_at(&mut self, index: usize) -> &mut Self {
+            let root = self.id();
+            self.commands().add(move |world: &mut World| {
+                remove_span(world, root, index);
+            });
+            self
+        }
+
+        fn replace_span(&mut self, index: usize, span: TextSpan) -> Entity {
+            let root = self.id();
+            let span = self.commands().spawn(span).id();
+            self.commands().add(move |world: &mut World| {
+                insert_span(world, root, index, span);
+                remove_span(world, root, index + 1);
+            });
+            span
+        }
+
+        fn clear_spans(&mut self) -> &mut Self {
+            let root = self.id();
+            self.commands().add(move |world: &mut World| {
+                clear_spans(world, root);
+            });
+            self
+        }
+
+        fn set_plain_text(&mut self, text: impl Into<String>) -> Entity {
+            let root = self.id();
+            let span = self
+                .commands()
+                .spawn(TextSpan(TextSection::new(text, TextStyle::default())))
+                .id();
+            self.commands().add(move |world: &mut World| {
+                set_plain_text(world, root, span);
+            });
+            span
+        }
+    }
+
+    /// Edits the spans of a `TextSpans` parent immediately, like [`SpanCommandsExt`].
+    pub trait SpanWorldExt {
+        /// The spans in section order, as of now rather than the last sync.
+        fn section_spans(&mut self) -> Vec<Entity>;
+
+        /// Adds `span` after the last section, returning its entity.
+        fn append_span(&mut self, span: TextSpan) -> Entity;
+
+        /// Adds `span` so it becomes the section at `index`, returning its entity.
+        fn insert_span_at(&mut self, index: usize, span: TextSpan) -> Entity;
+
+        /// Despawns the span of the section at `index`, returning it.
+        /// Spans nested below it take its place.
+        fn remove_span_at(&mut self, index: usize) -> Option<Entity>;
+
+        /// Despawns the span of the section at `index` and puts `span` in its place,
+        /// returning its entity.
+        fn replace_span(&mut self, index: usize, span: TextSpan) -> Entity;
+
+        /// Despawns every span, group and embedded `TextSpans` parent directly below the parent
+        /// (with their descendants), keeping other children, and returns them.
+        fn clear_spans(&mut self) -> Vec<Entity>;
+
+        /// Replaces every span with a single one, styled like the first section,
+        /// returning its entity.
+        fn set_plain_text(&mut self, text: impl Into<String>) -> Entity;
+    }
+
+    impl SpanWorldExt for EntityWorldMut<'_> {
+        fn section_spans(&mut self) -> Vec<Entity> {
+            let root = self.id();
+            self.world_scope(|world| {
+                SystemState::<SpanHierarchy>::new(world)
+                    .get(world)
+                    .section_spans(root)
+            })
+        }
+
+        fn append_span(&mut self, span: TextSpan) -> Entity {
+            self.insert_span_at(usize::MAX, span)
+        }
+
+        fn insert_span_at(&mut self, index: usize, span: TextSpan) -> Entity {
+            let root = self.id();
+            self.world_scope(|world| {
+                let span = world.spawn(span).id();
+                insert_span(world, root, index, span);
+                span
+            })
+        }
+
+        fn remove_span_at(&mut self, index: usize) -> Option<Entity> {
+            let root = self.id();
+            self.world_scope(|world| remove_span(world, root, index))
+        }
+
+        fn replace_span(&mut self, index: usize, span: TextSpan) -> Entity {
+            let span = self.insert_span_at(index, span);
+            self.remove_span_at(index + 1);
+            span
+        }
+
+        fn clear_spans(&mut self) -> Vec<Entity> {
+            let root = self.id();
+            self.world_scope(|world| clear_spans(world, root))
+        }
+
+        fn set_plain_text(&mut self, text: impl Into<String>) -> Entity {
+            let root = self.id();
+            self.world_scope(|world| {
+                let span = world
+                    .spawn(TextSpan(TextSection::new(text, TextStyle::default())))
+                    .id();
+                set_plain_text(world, root, span);
+                span
+            })
+        }
+    }
+
+    /// Places the span entity `span` below `root`, so it becomes the section at `index`
+    /// (or the last section).
+    ///
+    /// `span` is despawned if `root` isn't a `TextSpans` parent (anymore).
+    fn insert_span(world: &mut World, root: Entity, index: usize, span: Entity) {
+        if !world.entities().contains(span) {
+            return;
+        }
+        let mut state = SystemState::<SpanHierarchy>::new(world);
+        if !state.get(world).roots.contains(root) {
+            world.entity_mut(span).despawn_recursive();
+            return;
+        }
+        let hierarchy = state.get(world);
+        let next = hierarchy.section_spans(root).get(index).copied();
+        let parent = next
+            .and_then(|next| hierarchy.parent_of(next))
+            .unwrap_or(root);
+        let siblings = hierarchy.ordered_children(parent);
+        let link = match next {
+            Some(next) => hierarchy.links.contains(next),
+            None => hierarchy.linked.contains(parent) || siblings.is_empty(),
+        };
+        // Keep the new span in place among siblings sorted by `SpanOrder`
+        let order = match next {
+            Some(next) => hierarchy.orders.get(next).ok().copied(),
+            None => siblings
+                .iter()
+                .filter_map(|&sibling| hierarchy.orders.get(sibling).ok())
+                .map(|order| order.0)
+                .reduce(f64::max)
+                .map(|max| SpanOrder(max + 1.0)),
+        };
+
+        if let Some(order) = order {
+            world.entity_mut(span).insert(order);
+        }
+        if link {
+            world.entity_mut(span).insert(SpanOf(parent));
+            world.flush();
+            if let (Some(next), Some(mut spans)) = (next, world.get_mut::<Spans>(parent)) {
+                spans.0.retain(|&entity| entity != span);
+                let position = spans.0.iter().position(|&entity| entity == next);
+                let position = position.unwrap_or(spans.0.len());
+                spans.0.insert(position, span);
+            }
+        } else {
+            let children = world.get::<Children>(parent);
+            let position = next
+                .and_then(|next| children?.iter().position(|&child| child == next))
+                .or_else(|| children.map(|children| children.len()))
+                .unwrap_or_default();
+            world.entity_mut(parent).insert_children(position, &[span]);
+        }
+    }
+
+    /// Despawns the span of the section at `index` of `root`, returning it.
+    ///
+    /// The spans, groups and `TextSpans` parents nested below it are moved into its place
+    /// first, so only its own section is removed.
+    fn remove_span(world: &mut World, root: Entity, index: usize) -> Option<Entity> {
+        let mut state = SystemState::<SpanHierarchy>::new(world);
+        let hierarchy = state.get(world);
+        let span = *hierarchy.section_spans(root).get(index)?;
+        let nested = hierarchy.nested_spans(span);
+        let parent = hierarchy.parent_of(span);
+        let linked = hierarchy.links.contains(span);
+        let order = hierarchy.orders.get(span).ok().copied();
+
+        if let (Some(parent), false) = (parent, nested.is_empty()) {
+            for &child in &nested {
+                let mut child = world.entity_mut(child);
+                child.remove::<SpanOf>().remove_parent();
+                // Sort among the new siblings like the removed span
+                match order {
+                    Some(order) => child.insert(order),
+                    None => child.remove::<SpanOrder>(),
+                };
+            }
+            if linked {
+                for &child in &nested {
+                    world.entity_mut(child).insert(SpanOf(parent));
+                }
+                world.flush();
+                if let Some(mut spans) = world.get_mut::<Spans>(parent) {
+                    spans.0.retain(|entity| !nested.contains(entity));
+                    let position = spans.0.iter().position(|&entity| entity == span);
+                    let position = position.unwrap_or(spans.0.len());
+                    spans.0.splice(position..position, nested.iter().copied());
                 }
             } else {
-                error!("Missing `Text` for parent {parent:?}");
+                let position = world
+                    .get::<Children>(parent)
+                    .and_then(|children| children.iter().position(|&child| child == span))
+                    .unwrap_or_default();
+                world.entity_mut(parent).insert_children(position, &nested);
+            }
+        }
+        world.entity_mut(span).despawn_recursive();
+        Some(span)
+    }
+
+    /// Despawns the spans, groups and embedded `TextSpans` parents directly below `root`,
+    /// returning them.
+    fn clear_spans(world: &mut World, root: Entity) -> Vec<Entity> {
+        let mut state = SystemState::<SpanHierarchy>::new(world);
+        let spans = state.get(world).nested_spans(root);
+        for &span in &spans {
+            world.entity_mut(span).despawn_recursive();
+        }
+        spans
+    }
+
+    /// Replaces the spans of `root` with the span entity `span`, styled like the first section.
+    fn set_plain_text(world: &mut World, root: Entity, span: Entity) {
+        let mut state = SystemState::<SpanHierarchy>::new(world);
+        let hierarchy = state.get(world);
+        let style = hierarchy
+            .section_spans(root)
+            .first()
+            .and_then(|&first| hierarchy.span(first))
+            .map(|(_, style)| style.clone());
+        if let (Some(style), Some(mut text_span)) = (style, world.get_mut::<TextSpan>(span)) {
+            text_span.0.style = style;
+        }
+        clear_spans(world, root);
+        insert_span(world, root, usize::MAX, span);
+    }
+
+    /// Builds a `TextSpans` parent and its spans, for when the spans come from loops or data
+    /// rather than a [`text!`](crate::text) literal.
+    ///
+    /// Spans are linked to the parent through [`SpanOf`], in the order they were added.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_text_span_entities::prelude::*;
+    /// # #[derive(Component)] struct Link(&'static str);
+    /// # let mut world = World::new();
+    /// let parent = TextSpansBuilder::new()
+    ///     .span("Hello, ")
+    ///     .styled("world", TextStyle { font_size: 40.0, ..default() })
+    ///     .with(Link("https://example.com/"))
+    ///     .group(|group| group.span("!").span("!"))
+    ///     .with(SpanStyle { color: Some(Color::WHITE), ..default() })
+    ///     .spawn(&mut world)
+    ///     .id();
+    /// ```
+    pub struct TextSpansBuilder<B = TextBundle> {
+        parent: B,
+        spans: SpanGroupBuilder,
+    }
+
+    impl TextSpansBuilder {
+        /// A builder for a UI `TextSpans` parent, with no spans.
+        pub fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    impl Default for TextSpansBuilder {
+        fn default() -> Self {
+            Self {
+                parent: TextBundle::default(),
+                spans: SpanGroupBuilder::default(),
+            }
+        }
+    }
+
+    impl<B: Bundle> TextSpansBuilder<B> {
+        /// Spawns the parent with `bundle` (e.g. a [`Text2dBundle`]) instead of a default
+        /// [`TextBundle`]. [`TextSpans`] is always added.
+        pub fn parent_bundle<P: Bundle>(self, bundle: P) -> TextSpansBuilder<P> {
+            TextSpansBuilder {
+                parent: bundle,
+                spans: self.spans,
+            }
+        }
+
+        /// Adds a default-styled span.
+        pub fn span(self, text: impl Into<String>) -> Self {
+            self.styled(text, TextStyle::default())
+        }
+
+        /// Adds a span styled with `style`.
+        pub fn styled(mut self, text: impl Into<String>, style: TextStyle) -> Self {
+            self.spans = self.spans.styled(text, style);
+            self
+        }
+
+        /// Inserts `bundle` on the last span or group added.
+        ///
+        /// # Panics
+        ///
+        /// If no span or group was added yet.
+        pub fn with(mut self, bundle: impl Bundle) -> Self {
+            self.spans = self.spans.with(bundle);
+            self
+        }
+
+        /// Adds a group, with the spans and groups added by `build`.
+        ///
+        /// The group has a default [`SpanStyle`], which can be replaced through [`Self::with`].
+        pub fn group(mut self, build: impl FnOnce(SpanGroupBuilder) -> SpanGroupBuilder) -> Self {
+            self.spans = self.spans.group(build);
+            self
+        }
+
+        /// Spawns the parent into `target`, with its spans linked once the target's commands
+        /// are applied (immediately for a [`World`]).
+        pub fn spawn<S: SpanSpawner>(self, target: &mut S) -> S::Parent<'_> {
+            target.spawn_spans(self)
+        }
+    }
+
+    /// The spans of a group added through [`TextSpansBuilder::group`].
+    #[derive(Default)]
+    pub struct SpanGroupBuilder {
+        nodes: Vec<SpanNode>,
+    }
+
+    impl SpanGroupBuilder {
+        /// Adds a default-styled span.
+        pub fn span(self, text: impl Into<String>) -> Self {
+            self.styled(text, TextStyle::default())
+        }
+
+        /// Adds a span styled with `style`.
+        pub fn styled(mut self, text: impl Into<String>, style: TextStyle) -> Self {
+            self.nodes.push(SpanNode {
+                span: Some(TextSpan(TextSection::new(text, style))),
+                bundles: Vec::new(),
+                children: Vec::new(),
+            });
+            self
+        }
+
+        /// Inserts `bundle` on the last span or group added.
+        ///
+        /// # Panics
+        ///
+        /// If no span or group was added yet.
+        pub fn with(mut self, bundle: impl Bundle) -> Self {
+            self.nodes
+                .last_mut()
+                .expect("`with` follows a span or group")
+                .bundles
+                .push(Box::new(move |entity: &mut EntityWorldMut| {
+                    entity.insert(bundle);
+                }));
+            self
+        }
+
+        /// Adds a nested group, with the spans and groups added by `build`.
+        pub fn group(mut self, build: impl FnOnce(SpanGroupBuilder) -> SpanGroupBuilder) -> Self {
+            self.nodes.push(SpanNode {
+                span: None,
+                bundles: Vec::new(),
+                children: build(SpanGroupBuilder::default()).nodes,
+            });
+            self
+        }
+
+        /// Spawns the spans and groups, linked to `parent`.
+        fn spawn(self, world: &mut World, parent: Entity) {
+            for node in self.nodes {
+                let mut entity = match node.span {
+                    Some(span) => world.spawn((span, SpanOf(parent))),
+                    None => world.spawn((SpanStyle::default(), SpanOf(parent))),
+                };
+                for insert in node.bundles {
+                    insert(&mut entity);
+                }
+                let entity = entity.id();
+                SpanGroupBuilder {
+                    nodes: node.children,
+                }
+                .spawn(world, entity);
+            }
+            world.flush();
+        }
+    }
+
+    /// A span (or group, without a `TextSpan`) to be spawned by a [`TextSpansBuilder`].
+    struct SpanNode {
+        span: Option<TextSpan>,
+        bundles: Vec<InsertBundle>,
+        children: Vec<SpanNode>,
+    }
+
+    type InsertBundle = Box<dyn FnOnce(&mut EntityWorldMut) + Send + Sync>;
+
+    /// Where a [`TextSpansBuilder`] can be spawned: [`Commands`], a [`ChildBuilder`] or a
+    /// [`World`].
+    pub trait SpanSpawner {
+        /// The spawned parent
+        type Parent<'a>
+        where
+            Self: 'a;
+
+        /// Spawns the parent of `builder` and its spans.
+        fn spawn_spans<B: Bundle>(&mut self, builder: TextSpansBuilder<B>) -> Self::Parent<'_>;
+    }
+
+    impl SpanSpawner for Commands<'_, '_> {
+        type Parent<'a>
+            = EntityCommands<'a>
+        where
+            Self: 'a;
+
+        fn spawn_spans<B: Bundle>(&mut self, builder: TextSpansBuilder<B>) -> Self::Parent<'_> {
+            let mut parent = self.spawn((builder.parent, TextSpans));
+            let root = parent.id();
+            parent.commands().add(move |world: &mut World| {
+                builder.spans.spawn(world, root);
+            });
+            parent
+        }
+    }
+
+    impl SpanSpawner for ChildBuilder<'_> {
+        type Parent<'a>
+            = EntityCommands<'a>
+        where
+            Self: 'a;
+
+        fn spawn_spans<B: Bundle>(&mut self, builder: TextSpansBuilder<B>) -> Self::Parent<'_> {
+            let mut parent = self.spawn((builder.parent, TextSpans));
+            let root = parent.id();
+            parent.commands().add(move |world: &mut World| {
+                builder.spans.spawn(world, root);
+            });
+            parent
+        }
+    }
+
+    impl SpanSpawner for World {
+        type Parent<'a> = EntityWorldMut<'a>;
+
+        fn spawn_spans<B: Bundle>(&mut self, builder: TextSpansBuilder<B>) -> Self::Parent<'_> {
+            let root = self.spawn((builder.parent, TextSpans)).id();
+            builder.spans.spawn(self, root);
+            self.entity_mut(root)
+        }
+    }
+
+    /// The problems [`validate_spans`] found in the span hierarchies of a `World`.
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct SpanReport {
+        /// Ordered by entity
+        pub issues: Vec<SpanIssue>,
+    }
+
+    impl SpanReport {
+        /// Whether no issues were found
+        pub fn is_valid(&self) -> bool {
+            self.issues.is_empty()
+        }
+    }
+
+    impl std::fmt::Display for SpanReport {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            for issue in &self.issues {
+                writeln!(f, "{issue}")?;
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct SpanIssue {
+        pub entity: Entity,
+        pub kind: SpanIssueKind,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum SpanIssueKind {
+        /// A `TextSpan` with no `TextSpans` parent above it, so it is never synced
+        Orphan,
+        /// A `TextSpans` parent with no `Text` (or other [`TextSink`]) to sync into
+        MissingText,
+        /// A `TextSpans` parent that is also a `TextSpan`, so it is never synced
+        ParentIsSpan,
+        /// A descendant of `parent` that is neither a `TextSpan`, a group, an embedded
+        /// `TextSpans` parent, nor a [`SpanDecoration`]
+        MissingTextSpan { parent: Entity },
+        /// A span with an empty value
+        EmptySpan,
+        /// A `TextSpan` or [`SpanStyle`] whose font isn't in `Assets<Font>`
+        UnloadedFont { font: AssetId<Font> },
+    }
+
+    impl std::fmt::Display for SpanIssue {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let entity = self.entity;
+            match self.kind {
+                SpanIssueKind::Orphan => write!(f, "No `TextSpans` parent for span {entity:?}"),
+                SpanIssueKind::MissingText => write!(f, "Missing `Text` for parent {entity:?}"),
+                SpanIssueKind::ParentIsSpan => {
+                    write!(f, "Parent {entity:?} is also a `TextSpan`")
+                }
+                SpanIssueKind::MissingTextSpan { parent } => {
+                    write!(
+                        f,
+                        "Missing `TextSpan` for child {entity:?} for parent {parent:?}"
+                    )
+                }
+                SpanIssueKind::EmptySpan => write!(f, "Empty value for span {entity:?}"),
+                SpanIssueKind::UnloadedFont { font } => {
+                    write!(f, "Font {font:?} of {entity:?} isn't loaded")
+                }
+            }
+        }
+    }
+
+    /// Checks every span hierarchy in `world`, without syncing it.
+    ///
+    /// Fonts are only checked if `Assets<Font>` exists.
+    #[allow(clippy::type_complexity)]
+    pub fn validate_spans(world: &mut World) -> SpanReport {
+        let sinks = match world.get_resource::<TextSinkIds>() {
+            Some(sinks) => sinks.0.clone(),
+            None => vec![world.init_component::<Text>()],
+        };
+        let mut state = SystemState::<(
+            SpanHierarchy,
+            Query<(Entity, &TextSpan, Option<&SpanValue>), Without<TextSpans>>,
+            Query<Entity, (With<TextSpans>, Without<TextSpan>)>,
+            Query<Entity, (With<TextSpans>, With<TextSpan>)>,
+            Query<(Entity, &SpanStyle)>,
+            Option<Res<Assets<Font>>>,
+        )>::new(world);
+        let (hierarchy, spans, roots, span_roots, styles, fonts) = state.get(world);
+
+        let mut issues = Vec::new();
+        let mut issue = |entity, kind| issues.push(SpanIssue { entity, kind });
+        let unloaded = |font: &Handle<Font>| {
+            let unloaded = fonts.as_ref().is_some_and(|fonts| !fonts.contains(font));
+            unloaded.then(|| SpanIssueKind::UnloadedFont { font: font.id() })
+        };
+        for (entity, span, value) in &spans {
+            let root = hierarchy
+                .parent_of(entity)
+                .and_then(|parent| hierarchy.find_roots(parent).next());
+            if root.is_none() {
+                issue(entity, SpanIssueKind::Orphan);
+            }
+            if value.map_or(span.0.value.is_empty(), |value| value.0.is_empty()) {
+                issue(entity, SpanIssueKind::EmptySpan);
+            }
+            if let Some(kind) = unloaded(&span.0.style.font) {
+                issue(entity, kind);
+            }
+        }
+        for (entity, style) in &styles {
+            if let Some(kind) = style.font.as_ref().and_then(unloaded) {
+                issue(entity, kind);
             }
         }
+        for entity in &span_roots {
+            issue(entity, SpanIssueKind::ParentIsSpan);
+        }
+        for parent in &roots {
+            let entity = world.entity(parent);
+            if !hierarchy.is_embedded(parent) && !sinks.iter().any(|&id| entity.contains_id(id)) {
+                issue(parent, SpanIssueKind::MissingText);
+            }
+            for child in hierarchy.descendants_depth_first(parent) {
+                if !hierarchy.spans.contains(child)
+                    && !hierarchy.styles.contains(child)
+                    && !hierarchy.roots.contains(child)
+                {
+                    issue(child, SpanIssueKind::MissingTextSpan { parent });
+                }
+            }
+        }
+
+        issues.sort_by_key(|issue| issue.entity);
+        SpanReport { issues }
+    }
+
+    /// Logs the issues found by [`validate_spans`], e.g. as a system run from a debug key.
+    pub fn lint_spans(world: &mut World) {
+        for issue in validate_spans(world).issues {
+            warn!("{issue}");
+        }
+    }
+
+    /// A [`Command`] that runs [`lint_spans`].
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct ValidateSpans;
+
+    impl Command for ValidateSpans {
+        fn apply(self, world: &mut World) {
+            lint_spans(world);
+        }
     }
 }
 
@@ -87,9 +2457,13 @@ mod helper {
 
     // pub fn spawn_children(commands: &mut Commands, components: impl Bundle) {}
 
-    /// A macro that creates a `TextSpans` `TextBundle` entity with the given `TextSpan` spans as children.
+    /// A macro that creates a `TextSpans` `TextBundle` entity with the given `TextSpan` spans,
+    /// linked to it through `SpanOf`. A shorthand for a [`TextSpansBuilder`] spawned into
+    /// `Commands`, a `ChildBuilder` or a `World`.
+    ///
+    /// Returns the parent `EntityCommands` (or `EntityWorldMut`).
     ///
-    /// Returns the parent `EntityCommands`.
+    /// [`TextSpansBuilder`]: crate::prelude::TextSpansBuilder
     ///
     /// # Usage
     /// ```
@@ -160,28 +2534,23 @@ mod helper {
 
         // main
         // converts text!(commands, [("text", style, Component), ("text", style, Component)])
-        // into text!(@process TextBundle ; commands; [["text", style, Component], ["text", style, Component]])
+        // into text!(@process TextBundle::default() ; commands; [["text", style, Component], ["text", style, Component]])
         ( $commands:expr, [ $( ( $($tt:tt)* ) ),* ]) => {
             {
                 text!( @trace $commands, [ $( ( $($tt)* ) ),* ] );
-                text!( @process bevy::ui::node_bundles::TextBundle ; $commands ; $( [ $($tt)* ] )* )
+                text!( @process bevy::ui::node_bundles::TextBundle::default() ; $commands ; $( [ $($tt)* ] )* )
             }
         };
         ( @process $bundle:expr ; $commands:expr ; $( [ $($tt:tt)* ] )* ) => {
             {
-                use bevy::{
-                    hierarchy::{BuildChildren as _, ChildBuild as _},
-                    text::TextSection,
-                    ui::node_bundles::TextBundle,
-                };
-                use $crate::prelude::{TextSpan, TextSpans};
-                let mut parent = $commands.spawn((TextBundle::default(), TextSpans));
-                parent.with_children(|parent| {
-                    $(
-                        text!(@parse_inputs parent ; [ $($tt)* ]);
-                    )*
-                });
-                parent
+                #[allow(unused_mut)]
+                let mut builder = $crate::prelude::TextSpansBuilder::new().parent_bundle($bundle);
+                $(
+                    builder = text!(@parse_inputs builder ; [ $($tt)* ]);
+                )*
+                // Method syntax, so `$commands` can be owned or a mutable reference
+                use $crate::prelude::SpanSpawner as _;
+                $commands.spawn_spans(builder)
             }
         };
 
@@ -194,41 +2563,33 @@ mod helper {
         };
 
         // Matches: text!(&mut commands, [ ( "Foo", {} ), ( "Bar", { color: red } ) ] );
-        ( @parse_inputs $parent:expr ; [ $text:expr, { $( $key:tt : $value:expr ),* } ] ) => {
-            text!( @parse_inputs $parent ; [ $text, text!( @expand_styles $( $key : $value ),* ) ] )
+        ( @parse_inputs $builder:ident ; [ $text:expr, { $( $key:tt : $value:expr ),* } ] ) => {
+            text!( @parse_inputs $builder ; [ $text, text!( @expand_styles $( $key : $value ),* ) ] )
         };
         // Matches: text!(&mut commands, [ ( "Foo", {} ), ( "Bar", { color: red }, A ) ] );
-        ( @parse_inputs $parent:expr ; [ $text:expr, { $( $key:tt : $value:expr ),* }, $components:expr ] ) => {
-            text!( @parse_inputs $parent ; [ $text, text!( @expand_styles $( $key : $value ),* ), $components ] )
+        ( @parse_inputs $builder:ident ; [ $text:expr, { $( $key:tt : $value:expr ),* }, $components:expr ] ) => {
+            text!( @parse_inputs $builder ; [ $text, text!( @expand_styles $( $key : $value ),* ), $components ] )
         };
         // Matches: text!(&mut commands, [ ( "Foo" ) ] );
         // Matches: text!(&mut commands, [ ( "Foo" ), ( "Bar" ) ] );
-        ( @parse_inputs $parent:expr ; [ $text:expr ]) => {
-            text!(@spawn_span $parent ; @text $text ; @style Default::default() ; @components ())
+        ( @parse_inputs $builder:ident ; [ $text:expr ]) => {
+            $builder.span($text)
         };
         // Matches: text!(&mut commands, [ ( "Foo", style.clone() ), ( "Bar", style ) ] );
-        ( @parse_inputs $parent:expr ; [ $text:expr, $style:expr ] ) => {
-            text!(@spawn_span $parent ; @text $text ; @style $style ; @components ())
+        ( @parse_inputs $builder:ident ; [ $text:expr, $style:expr ] ) => {
+            $builder.styled($text, $style)
         };
         // Matches: text!(&mut commands, [ ( "Foo", style.clone(), A ), ( "Bar", style, B ) ] );
         // Matches: text!(&mut commands, [ ( text_expr0, style_expr0, components_expr0 ), /* ... */ ] );
         // Matches: text!(&mut commands, [ ( text_expr0, style_expr0, components_expr0 ), ( text_expr1, style_expr1, components_expr1 ), /* ... */ ] );
-        ( @parse_inputs $parent:expr ; [ $text:expr, $style:expr, $components:expr ] ) => {
-            text!( @spawn_span $parent ; @text $text ; @style $style ; @components $components )
+        ( @parse_inputs $builder:ident ; [ $text:expr, $style:expr, $components:expr ] ) => {
+            $builder.styled($text, $style).with($components)
         };
-        ( @parse_inputs $parent:expr ; $($tt:tt)* ) => {
-            // should no longer be reachable
-            text!(@unhandled $($tt)*);
-        };
-
-        ( @spawn_span $parent:expr ; @text $text:expr ; @style $style:expr ; @components $components:expr ) => {
-            $parent.spawn((
-                TextSpan(TextSection {
-                    value: $text.into(),
-                    style: $style,
-                }),
-                $components,
-            ));
+        ( @parse_inputs $($tt:tt)* ) => {
+            {
+                // should no longer be reachable
+                text!(@unhandled $($tt)*);
+            }
         };
 
         // development
@@ -253,12 +2614,11 @@ mod helper {
 
         // main
         // converts text2d!(commands, [("text", style, Component), ("text", style, Component)])
-        // into text!(@process Text2dBundle ; commands; [["text", style, Component], ["text", style, Component]])
-        // into text!(@process commands; [["text", style, Component], ["text", style, Component]])
+        // into text!(@process Text2dBundle::default() ; commands; [["text", style, Component], ["text", style, Component]])
         ( $commands:expr, [ $( ( $($tt:tt)* ) ),* ]) => {
             {
                 text!( @trace $commands, [ $( ( $($tt)* ) ),* ] );
-                text!( @process bevy::text::Text2dBundle ; $commands ; $( [ $($tt)* ] )* )
+                text!( @process bevy::text::Text2dBundle::default() ; $commands ; $( [ $($tt)* ] )* )
             }
         };
     }
@@ -268,7 +2628,11 @@ mod helper {
 
 #[cfg(test)]
 mod test {
-    use bevy::{color::Color, prelude::Component, text::TextStyle};
+    use bevy::{
+        color::Color,
+        prelude::{BuildChildren as _, Component},
+        text::TextStyle,
+    };
 
     use super::helper::{text, text2d};
 
@@ -286,6 +2650,10 @@ mod test {
             ..Default::default()
         };
         text!(&mut commands, [(s), (t), (u)]);
+        text!(commands, [(s), (t), (u)]);
+        commands.spawn_empty().with_children(|parent| {
+            text!(parent, [(s), (t), (u)]);
+        });
         text!(
             &mut commands,
             [(s, style.clone()), (t, style.clone()), (u, style.clone())]
@@ -377,4 +2745,1504 @@ mod test {
         );
         drop(style);
     }
+
+    mod sync {
+        use std::time::Duration;
+
+        use bevy::{
+            ecs::{schedule::ScheduleLabel, world::Command},
+            prelude::*,
+            tasks::{ComputeTaskPool, TaskPoolBuilder},
+        };
+
+        use crate::lib::{SpanLayouts, SpanLinks};
+        use crate::prelude::*;
+
+        fn section(value: impl Into<String>) -> TextSection {
+            TextSection {
+                value: value.into(),
+                ..Default::default()
+            }
+        }
+
+        fn app() -> App {
+            app_with(SyncBackend::Polling)
+        }
+
+        fn app_with(backend: SyncBackend) -> App {
+            let mut app = App::new();
+            app.add_plugins((
+                TaskPoolPlugin::default(),
+                TsePlugin {
+                    backend,
+                    ..Default::default()
+                },
+            ));
+            app
+        }
+
+        fn spawn_parent(app: &mut App, count: usize) -> (Entity, Vec<Entity>) {
+            let mut spans = Vec::new();
+            let parent = app
+                .world_mut()
+                .spawn((TextBundle::default(), TextSpans))
+                .with_children(|parent| {
+                    for i in 0..count {
+                        spans.push(parent.spawn(TextSpan(section(i.to_string()))).id());
+                    }
+                })
+                .id();
+            (parent, spans)
+        }
+
+        fn errors(app: &App) -> Vec<SpanSyncError> {
+            let events = app.world().resource::<Events<SpanSyncError>>();
+            events.iter_current_update_events().copied().collect()
+        }
+
+        fn values(app: &App, parent: Entity) -> Vec<String> {
+            let text = app.world().get::<Text>(parent).unwrap();
+            text.sections.iter().map(|s| s.value.clone()).collect()
+        }
+
+        #[test]
+        fn patches_only_changed_sections() {
+            let mut app = app();
+            let (parent, spans) = spawn_parent(&mut app, 3);
+            app.update();
+            assert_eq!(values(&app, parent), ["0", "1", "2"]);
+            assert_eq!(
+                app.world().resource::<SpanLayouts>().stats.cloned_sections,
+                3
+            );
+
+            app.world_mut()
+                .get_mut::<TextSpan>(spans[1])
+                .unwrap()
+                .0
+                .value = "one".into();
+            app.update();
+            assert_eq!(values(&app, parent), ["0", "one", "2"]);
+            assert_eq!(
+                app.world().resource::<SpanLayouts>().stats.cloned_sections,
+                1
+            );
+        }
+
+        #[test]
+        fn identical_sections_do_not_change_text() {
+            #[derive(Resource, Default)]
+            struct TextChanges(usize);
+
+            let mut app = app();
+            app.init_resource::<TextChanges>();
+            app.add_systems(
+                PostUpdate,
+                (|texts: Query<(), Changed<Text>>, mut changes: ResMut<TextChanges>| {
+                    changes.0 += texts.iter().count();
+                })
+                .in_set(TseSet::PostSync),
+            );
+            let (parent, spans) = spawn_parent(&mut app, 2);
+            app.update();
+            assert_eq!(app.world().resource::<TextChanges>().0, 1);
+
+            // Rewriting a span with its current value patches nothing
+            app.world_mut()
+                .get_mut::<TextSpan>(spans[0])
+                .unwrap()
+                .0
+                .value = "0".into();
+            app.update();
+            assert_eq!(app.world().resource::<TextChanges>().0, 1);
+
+            // Neither does a rebuild that ends up with the same sections
+            app.world_mut()
+                .entity_mut(parent)
+                .insert(SpanStyle::default());
+            app.update();
+            assert_eq!(app.world().resource::<TextChanges>().0, 1);
+            assert_eq!(values(&app, parent), ["0", "1"]);
+        }
+
+        #[test]
+        fn shared_span_values_replace_the_span_value() {
+            let mut app = app();
+            let (first, first_spans) = spawn_parent(&mut app, 2);
+            let (second, second_spans) = spawn_parent(&mut app, 1);
+            let shared = SpanValue::from("shared");
+            app.world_mut()
+                .entity_mut(first_spans[1])
+                .insert(shared.clone());
+            app.world_mut().entity_mut(second_spans[0]).insert(shared);
+            app.update();
+            assert_eq!(values(&app, first), ["0", "shared"]);
+            assert_eq!(values(&app, second), ["shared"]);
+
+            app.world_mut()
+                .entity_mut(first_spans[1])
+                .insert(SpanValue::from("edited"));
+            app.update();
+            assert_eq!(values(&app, first), ["0", "edited"]);
+            assert_eq!(
+                app.world().resource::<SpanLayouts>().stats.cloned_sections,
+                1
+            );
+
+            // Without a `SpanValue`, the `TextSpan` value is used again
+            app.world_mut()
+                .entity_mut(first_spans[1])
+                .remove::<SpanValue>();
+            app.update();
+            assert_eq!(values(&app, first), ["0", "1"]);
+        }
+
+        #[test]
+        fn span_values_round_trip_through_reflection_and_scenes() {
+            let value = SpanValue::from("shared");
+            assert_eq!(
+                SpanValue::from_reflect(&*value.clone_value()),
+                Some(value.clone())
+            );
+
+            let mut app = app();
+            let (_, spans) = spawn_parent(&mut app, 1);
+            app.world_mut().entity_mut(spans[0]).insert(value.clone());
+            let scene = DynamicSceneBuilder::from_world(app.world())
+                .allow::<SpanValue>()
+                .extract_entity(spans[0])
+                .build();
+            let registry = app.world().resource::<AppTypeRegistry>().clone();
+            let ron = scene.serialize(&registry.read()).unwrap();
+            assert!(ron.contains("\"shared\""), "{ron}");
+
+            let mut world = World::new();
+            world.insert_resource(registry);
+            let mut entities = default();
+            scene.write_to_world(&mut world, &mut entities).unwrap();
+            assert_eq!(world.get::<SpanValue>(entities[&spans[0]]), Some(&value));
+        }
+
+        #[test]
+        fn inserts_and_removes_spans_mid_list() {
+            let mut app = app();
+            let (parent, spans) = spawn_parent(&mut app, 3);
+            app.update();
+
+            let new = app.world_mut().spawn(TextSpan(section("new"))).id();
+            app.world_mut()
+                .entity_mut(parent)
+                .insert_children(1, &[new]);
+            app.update();
+            assert_eq!(values(&app, parent), ["0", "new", "1", "2"]);
+            assert_eq!(
+                app.world().resource::<SpanLayouts>().stats.cloned_sections,
+                1
+            );
+
+            app.world_mut().entity_mut(spans[1]).despawn_recursive();
+            app.update();
+            assert_eq!(values(&app, parent), ["0", "new", "2"]);
+            assert_eq!(
+                app.world().resource::<SpanLayouts>().stats.cloned_sections,
+                0
+            );
+        }
+
+        #[test]
+        fn despawning_the_last_span_clears_the_text() {
+            let mut app = app();
+            let (parent, spans) = spawn_parent(&mut app, 1);
+            app.update();
+            assert_eq!(values(&app, parent), ["0"]);
+
+            app.world_mut().entity_mut(spans[0]).despawn_recursive();
+            app.update();
+            assert!(values(&app, parent).is_empty());
+        }
+
+        #[test]
+        fn removing_text_span_removes_the_section() {
+            let mut app = app();
+            let (parent, spans) = spawn_parent(&mut app, 3);
+            app.update();
+
+            app.world_mut().entity_mut(spans[1]).remove::<TextSpan>();
+            app.update();
+            assert_eq!(values(&app, parent), ["0", "2"]);
+
+            app.world_mut()
+                .entity_mut(spans[1])
+                .insert(TextSpan(section("back")));
+            app.update();
+            assert_eq!(values(&app, parent), ["0", "back", "2"]);
+        }
+
+        #[test]
+        fn removing_the_parent_removes_the_section() {
+            let mut app = app();
+            let (parent, spans) = spawn_parent(&mut app, 2);
+            app.update();
+
+            app.world_mut().entity_mut(spans[0]).remove_parent();
+            app.update();
+            assert_eq!(values(&app, parent), ["1"]);
+
+            app.world_mut().entity_mut(spans[1]).remove_parent();
+            app.update();
+            assert!(values(&app, parent).is_empty());
+        }
+
+        #[test]
+        fn reparenting_a_span_resyncs_both_parents() {
+            let mut app = app();
+            let (first, spans) = spawn_parent(&mut app, 2);
+            let (second, _) = spawn_parent(&mut app, 1);
+            app.update();
+
+            app.world_mut().entity_mut(spans[1]).set_parent(second);
+            app.update();
+            assert_eq!(values(&app, first), ["0"]);
+            assert_eq!(values(&app, second), ["0", "1"]);
+
+            // Moving the last span away leaves the old parent empty
+            app.world_mut().entity_mut(spans[0]).set_parent(second);
+            app.update();
+            assert!(values(&app, first).is_empty());
+            assert_eq!(values(&app, second), ["0", "1", "0"]);
+        }
+
+        #[test]
+        fn span_entities_follow_section_order() {
+            let mut app = app();
+            let parent = app
+                .world_mut()
+                .spawn((TextBundle::default(), TextSpans))
+                .id();
+            let mut spans = Vec::new();
+            app.world_mut().entity_mut(parent).with_children(|parent| {
+                spans.push(parent.spawn(TextSpan(section("a"))).id());
+                // Nested spans are flattened in place
+                parent.spawn(TextSpan(section("b"))).with_children(|group| {
+                    spans.push(group.parent_entity());
+                    spans.push(group.spawn(TextSpan(section("c"))).id());
+                });
+                spans.push(parent.spawn(TextSpan(section("d"))).id());
+            });
+            app.update();
+            assert_eq!(values(&app, parent), ["a", "b", "c", "d"]);
+
+            let span_entities = app.world().get::<SpanEntities>(parent).unwrap();
+            assert_eq!(span_entities.entities(), spans);
+            assert_eq!(span_entities.get(2), Some(spans[2]));
+            assert_eq!(span_entities.index_of(spans[3]), Some(3));
+
+            app.world_mut().entity_mut(spans[0]).despawn_recursive();
+            app.update();
+            let span_entities = app.world().get::<SpanEntities>(parent).unwrap();
+            assert_eq!(span_entities.entities(), &spans[1..]);
+        }
+
+        #[test]
+        fn span_styles_cascade_through_groups() {
+            let red = Color::srgb(1.0, 0.0, 0.0);
+            let green = Color::srgb(0.0, 1.0, 0.0);
+            let blue = Color::srgb(0.0, 0.0, 1.0);
+
+            let mut app = app();
+            let mut group = Entity::PLACEHOLDER;
+            let parent = app
+                .world_mut()
+                .spawn((
+                    TextBundle::default(),
+                    TextSpans,
+                    SpanStyle {
+                        color: Some(red),
+                        ..Default::default()
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextSpan(section("a")));
+                    group = parent
+                        .spawn(SpanStyle {
+                            font_size: Some(40.0),
+                            ..Default::default()
+                        })
+                        .with_children(|group| {
+                            group.spawn(TextSpan(section("b")));
+                            group.spawn((
+                                TextSpan(section("c")),
+                                SpanStyle {
+                                    color: Some(blue),
+                                    ..Default::default()
+                                },
+                            ));
+                        })
+                        .id();
+                })
+                .id();
+            app.update();
+
+            let styles = |app: &App| -> Vec<(Color, f32)> {
+                let text = app.world().get::<Text>(parent).unwrap();
+                text.sections
+                    .iter()
+                    .map(|s| (s.style.color, s.style.font_size))
+                    .collect()
+            };
+            let default_size = TextStyle::default().font_size;
+            assert_eq!(values(&app, parent), ["a", "b", "c"]);
+            assert_eq!(
+                styles(&app),
+                [(red, default_size), (red, 40.0), (blue, 40.0)]
+            );
+
+            // Restyling the group restyles every span below it, without touching the spans
+            app.world_mut().get_mut::<SpanStyle>(group).unwrap().color = Some(green);
+            app.update();
+            assert_eq!(
+                styles(&app),
+                [(red, default_size), (green, 40.0), (blue, 40.0)]
+            );
+            assert_eq!(
+                app.world().resource::<SpanLayouts>().stats.cloned_sections,
+                0
+            );
+
+            app.world_mut().entity_mut(group).remove::<SpanStyle>();
+            app.update();
+            assert_eq!(
+                styles(&app),
+                [
+                    (red, default_size),
+                    (red, default_size),
+                    (blue, default_size)
+                ]
+            );
+        }
+
+        #[test]
+        fn hidden_spans_are_omitted_or_transparent() {
+            let mut app = app();
+            let (parent, spans) = spawn_parent(&mut app, 3);
+            app.update();
+
+            app.world_mut()
+                .entity_mut(spans[1])
+                .insert(Visibility::Hidden);
+            app.update();
+            assert_eq!(values(&app, parent), ["0", "2"]);
+            let span_entities = app.world().get::<SpanEntities>(parent).unwrap();
+            assert_eq!(span_entities.entities(), [spans[0], spans[2]]);
+
+            app.world_mut()
+                .entity_mut(parent)
+                .insert(SpanVisibilityMode::Preserve);
+            app.update();
+            assert_eq!(values(&app, parent), ["0", "1", "2"]);
+            let text = app.world().get::<Text>(parent).unwrap();
+            assert_eq!(text.sections[0].style.color.alpha(), 1.0);
+            assert_eq!(text.sections[1].style.color.alpha(), 0.0);
+
+            // Hidden spans keep their layout while their value changes
+            app.world_mut()
+                .get_mut::<TextSpan>(spans[1])
+                .unwrap()
+                .0
+                .value = "one".into();
+            app.update();
+            let text = app.world().get::<Text>(parent).unwrap();
+            assert_eq!(text.sections[1].value, "one");
+            assert_eq!(text.sections[1].style.color.alpha(), 0.0);
+
+            *app.world_mut().get_mut::<Visibility>(spans[1]).unwrap() = Visibility::Inherited;
+            app.update();
+            let text = app.world().get::<Text>(parent).unwrap();
+            assert_eq!(text.sections[1].style.color.alpha(), 1.0);
+        }
+
+        #[test]
+        fn hidden_groups_hide_their_spans() {
+            let mut app = app();
+            let mut group = Entity::PLACEHOLDER;
+            let parent = app
+                .world_mut()
+                .spawn((TextBundle::default(), TextSpans))
+                .with_children(|parent| {
+                    parent.spawn(TextSpan(section("a")));
+                    group = parent
+                        .spawn((SpanStyle::default(), Visibility::Hidden))
+                        .with_children(|group| {
+                            group.spawn(TextSpan(section("b")));
+                            group.spawn((TextSpan(section("c")), Visibility::Visible));
+                        })
+                        .id();
+                })
+                .id();
+            app.update();
+            assert_eq!(values(&app, parent), ["a", "c"]);
+
+            *app.world_mut().get_mut::<Visibility>(group).unwrap() = Visibility::Visible;
+            app.update();
+            assert_eq!(values(&app, parent), ["a", "b", "c"]);
+        }
+
+        #[test]
+        fn custom_sinks_receive_the_flattened_sections() {
+            #[derive(Component, Default)]
+            struct Labels(Vec<TextSection>);
+
+            impl TextSink for Labels {
+                fn sections(&self) -> &[TextSection] {
+                    &self.0
+                }
+
+                fn sections_mut(&mut self) -> &mut Vec<TextSection> {
+                    &mut self.0
+                }
+            }
+
+            let mut app = App::new();
+            app.add_plugins(TsePlugin::default().with_sink::<Labels>());
+            let (both, _) = spawn_parent(&mut app, 2);
+            app.world_mut().entity_mut(both).insert(Labels::default());
+            let (labels_only, spans) = spawn_parent(&mut app, 1);
+            app.world_mut()
+                .entity_mut(labels_only)
+                .remove::<Text>()
+                .insert(Labels::default());
+            app.update();
+            assert!(errors(&app).is_empty());
+            let label_values = |app: &App, parent| -> Vec<String> {
+                let labels = app.world().get::<Labels>(parent).unwrap();
+                labels.0.iter().map(|s| s.value.clone()).collect()
+            };
+            assert_eq!(values(&app, both), ["0", "1"]);
+            assert_eq!(label_values(&app, both), ["0", "1"]);
+            assert_eq!(label_values(&app, labels_only), ["0"]);
+
+            app.world_mut()
+                .get_mut::<TextSpan>(spans[0])
+                .unwrap()
+                .0
+                .value = "zero".into();
+            app.update();
+            assert_eq!(label_values(&app, labels_only), ["zero"]);
+
+            // A sink inserted after the first sync is filled, then patched
+            let (late, spans) = spawn_parent(&mut app, 2);
+            app.update();
+            app.world_mut().entity_mut(late).insert(Labels::default());
+            app.update();
+            assert_eq!(label_values(&app, late), ["0", "1"]);
+            app.world_mut()
+                .get_mut::<TextSpan>(spans[1])
+                .unwrap()
+                .0
+                .value = "one".into();
+            app.update();
+            assert_eq!(label_values(&app, late), ["0", "one"]);
+        }
+
+        #[test]
+        fn embedded_text_spans_are_flattened_inline() {
+            let mut app = app();
+            let (outer, outer_spans) = spawn_parent(&mut app, 2);
+            let name = app.world_mut().spawn(TextSpan(section("name"))).id();
+            let fragment = app
+                .world_mut()
+                .spawn((
+                    TextSpans,
+                    SpanStyle {
+                        font_size: Some(30.0),
+                        ..Default::default()
+                    },
+                ))
+                .add_child(name)
+                .id();
+            app.world_mut()
+                .entity_mut(outer)
+                .insert_children(1, &[fragment]);
+            let (other, _) = spawn_parent(&mut app, 1);
+            app.world_mut().entity_mut(other).add_child(fragment);
+            app.update();
+            assert!(errors(&app).is_empty());
+            assert_eq!(values(&app, outer), ["0", "1"]);
+            assert_eq!(values(&app, other), ["0", "name"]);
+
+            // Moving the fragment resyncs both parents
+            app.world_mut()
+                .entity_mut(outer)
+                .insert_children(1, &[fragment]);
+            app.update();
+            assert!(errors(&app).is_empty());
+            assert_eq!(values(&app, outer), ["0", "name", "1"]);
+            assert_eq!(values(&app, other), ["0"]);
+            let text = app.world().get::<Text>(outer).unwrap();
+            assert_eq!(text.sections[1].style.font_size, 30.0);
+            assert_eq!(
+                app.world().get::<SpanEntities>(outer).unwrap().entities(),
+                [outer_spans[0], name, outer_spans[1]]
+            );
+
+            // Changes inside the fragment reach every enclosing text
+            let outermost = app
+                .world_mut()
+                .spawn((TextBundle::default(), TextSpans))
+                .id();
+            app.world_mut().entity_mut(outermost).add_child(outer);
+            app.update();
+            app.world_mut().get_mut::<TextSpan>(name).unwrap().0.value = "renamed".into();
+            app.update();
+            assert_eq!(values(&app, outer), ["0", "renamed", "1"]);
+            assert_eq!(values(&app, outermost), ["0", "renamed", "1"]);
+
+            // Hiding the fragment hides its spans in every enclosing text
+            app.world_mut()
+                .entity_mut(fragment)
+                .insert(Visibility::Hidden);
+            app.update();
+            assert_eq!(values(&app, outer), ["0", "1"]);
+            assert_eq!(values(&app, outermost), ["0", "1"]);
+            *app.world_mut().get_mut::<Visibility>(fragment).unwrap() = Visibility::Inherited;
+            app.update();
+            assert_eq!(values(&app, outer), ["0", "renamed", "1"]);
+            assert_eq!(values(&app, outermost), ["0", "renamed", "1"]);
+        }
+
+        #[test]
+        fn linked_spans_coexist_with_children() {
+            for backend in [SyncBackend::Polling, SyncBackend::Observers] {
+                let mut app = app_with(backend);
+                let mut commands = app.world_mut().commands();
+                let parent = text!(&mut commands, [("a"), ("b")]).id();
+                app.world_mut().flush();
+                let icon = app.world_mut().spawn(NodeBundle::default()).id();
+                app.world_mut().entity_mut(parent).add_child(icon);
+                app.update();
+                assert!(errors(&app).is_empty());
+                assert_eq!(values(&app, parent), ["a", "b"]);
+                let spans = app
+                    .world()
+                    .get::<Spans>(parent)
+                    .unwrap()
+                    .entities()
+                    .to_vec();
+                assert_eq!(spans.len(), 2);
+
+                // Relinking moves the span to the other parent
+                let mut commands = app.world_mut().commands();
+                let other = text!(&mut commands, [("c")]).id();
+                app.world_mut().flush();
+                app.world_mut().entity_mut(spans[0]).insert(SpanOf(other));
+                app.update();
+                assert_eq!(values(&app, parent), ["b"], "{backend:?}");
+                assert_eq!(values(&app, other), ["c", "a"], "{backend:?}");
+                assert_eq!(
+                    app.world().get::<Spans>(parent).unwrap().entities(),
+                    [spans[1]]
+                );
+
+                app.world_mut().entity_mut(parent).despawn_recursive();
+                app.world_mut().flush();
+                assert!(!app.world().entities().contains(spans[1]));
+                assert!(!app.world().entities().contains(icon));
+                assert!(app.world().entities().contains(spans[0]));
+            }
+        }
+
+        #[test]
+        fn span_links_that_would_make_a_cycle_are_removed() {
+            for backend in [SyncBackend::Polling, SyncBackend::Observers] {
+                let mut app = app_with(backend);
+                let (parent, spans) = spawn_parent(&mut app, 2);
+                let linked = TextSpansBuilder::new()
+                    .span("a")
+                    .group(|group| group.span("b"))
+                    .spawn(app.world_mut())
+                    .id();
+                app.update();
+                let group = app.world().get::<Spans>(linked).unwrap().entities()[1];
+                let nested = app.world().get::<Spans>(group).unwrap().entities()[0];
+
+                // Both would hang the sync walking up from the span
+                app.world_mut()
+                    .entity_mut(spans[0])
+                    .insert(SpanOf(spans[0]));
+                app.world_mut().entity_mut(group).insert(SpanOf(nested));
+                app.update();
+                assert!(!app.world().entity(spans[0]).contains::<SpanOf>());
+                assert_eq!(app.world().get::<SpanOf>(group), Some(&SpanOf(linked)));
+                assert_eq!(values(&app, parent), ["0", "1"], "{backend:?}");
+                assert_eq!(values(&app, linked), ["a", "b"], "{backend:?}");
+                validate_spans(app.world_mut());
+            }
+        }
+
+        #[test]
+        fn linking_to_a_despawned_parent_does_not_panic() {
+            let mut app = app();
+            let parent = app.world_mut().spawn(TextSpans).id();
+            let mut commands = app.world_mut().commands();
+            commands.spawn((TextSpan(section("a")), SpanOf(parent)));
+            commands.entity(parent).despawn();
+            app.update();
+            assert!(app.world().resource::<SpanLinks>().pending.is_empty());
+        }
+
+        #[test]
+        fn span_links_are_mapped_when_spawning_scenes() {
+            let mut source = app();
+            let parent = source
+                .world_mut()
+                .spawn_text_spans(
+                    TextBundle::default(),
+                    [TextSpan(section("a")), TextSpan(section("b"))],
+                )
+                .id();
+            source.update();
+            let spans = source.world().get::<Spans>(parent).unwrap().entities();
+            let scene = DynamicSceneBuilder::from_world(source.world())
+                .extract_entities([parent].into_iter().chain(spans.iter().copied()))
+                .build();
+
+            // The scene's entity ids belong to other entities here
+            let mut app = app();
+            let others: Vec<Entity> = (0..8).map(|_| app.world_mut().spawn_empty().id()).collect();
+            let mut entities = default();
+            scene
+                .write_to_world(app.world_mut(), &mut entities)
+                .unwrap();
+            let spans: Vec<Entity> = spans.iter().map(|span| entities[span]).collect();
+            let parent = entities[&parent];
+            app.world_mut()
+                .entity_mut(parent)
+                .insert(TextBundle::default());
+            app.update();
+            assert!(errors(&app).is_empty());
+            assert_eq!(values(&app, parent), ["a", "b"]);
+            assert_eq!(app.world().get::<Spans>(parent).unwrap().entities(), spans);
+            assert_eq!(
+                app.world().get::<SpanEntities>(parent).unwrap().entities(),
+                spans
+            );
+            assert!(others
+                .iter()
+                .all(|&other| app.world().get::<Spans>(other).is_none()));
+
+            app.world_mut().entity_mut(spans[0]).remove::<SpanOf>();
+            app.update();
+            assert_eq!(values(&app, parent), ["b"]);
+        }
+
+        #[test]
+        fn post_sync_systems_see_the_flattened_text() {
+            #[derive(Resource, Default)]
+            struct Seen(Vec<String>);
+
+            let mut app = app();
+            app.init_resource::<Seen>();
+            app.add_systems(
+                PostUpdate,
+                (|texts: Query<&Text, With<TextSpans>>, mut seen: ResMut<Seen>| {
+                    seen.0 = texts
+                        .iter()
+                        .flat_map(|text| text.sections.iter().map(|s| s.value.clone()))
+                        .collect();
+                })
+                .in_set(TseSet::PostSync),
+            );
+            spawn_parent(&mut app, 2);
+            app.update();
+            assert_eq!(app.world().resource::<Seen>().0, ["0", "1"]);
+        }
+
+        #[test]
+        fn sync_policies_throttle_and_coalesce_changes() {
+            let mut app = app();
+            app.init_resource::<Time>();
+            let (throttled, throttled_spans) = spawn_parent(&mut app, 2);
+            let (on_request, on_request_spans) = spawn_parent(&mut app, 1);
+            app.world_mut()
+                .entity_mut(throttled)
+                .insert(SpanSyncPolicy::MaxRate(10.0));
+            app.world_mut()
+                .entity_mut(on_request)
+                .insert(SpanSyncPolicy::OnRequest);
+            app.update();
+            assert_eq!(values(&app, throttled), ["0", "1"]);
+            assert!(values(&app, on_request).is_empty());
+
+            let set_value = |app: &mut App, span, value: &str| {
+                app.world_mut().get_mut::<TextSpan>(span).unwrap().0.value = value.into();
+            };
+            set_value(&mut app, throttled_spans[0], "a");
+            set_value(&mut app, on_request_spans[0], "a");
+            app.update();
+            set_value(&mut app, throttled_spans[1], "b");
+            app.update();
+            assert_eq!(values(&app, throttled), ["0", "1"]);
+
+            // Both changes land once the rate allows it
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(100));
+            app.update();
+            assert_eq!(values(&app, throttled), ["a", "b"]);
+
+            assert!(values(&app, on_request).is_empty());
+            app.world_mut()
+                .entity_mut(on_request)
+                .insert(SpanSyncRequest);
+            app.update();
+            assert_eq!(values(&app, on_request), ["a"]);
+            assert!(!app.world().entity(on_request).contains::<SpanSyncRequest>());
+        }
+
+        #[test]
+        fn throttled_parents_sync_every_span_changed_in_a_frame() {
+            let mut app = app();
+            app.init_resource::<Time>();
+            let (parent, spans) = spawn_parent(&mut app, 2);
+            app.world_mut()
+                .entity_mut(parent)
+                .insert(SpanSyncPolicy::MaxRate(10.0));
+            app.update();
+
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(100));
+            for (span, value) in spans.iter().zip(["a", "b"]) {
+                app.world_mut().get_mut::<TextSpan>(*span).unwrap().0.value = value.into();
+            }
+            app.update();
+            assert_eq!(values(&app, parent), ["a", "b"]);
+
+            // A `Time` behind the last sync holds the parent instead of panicking
+            app.world_mut().insert_resource(Time::<()>::default());
+            app.world_mut()
+                .get_mut::<TextSpan>(spans[0])
+                .unwrap()
+                .0
+                .value = "c".into();
+            app.update();
+            assert_eq!(values(&app, parent), ["a", "b"]);
+        }
+
+        #[test]
+        fn span_commands_edit_by_section_index() {
+            let mut app = app();
+            let (children, child_spans) = spawn_parent(&mut app, 2);
+            let mut commands = app.world_mut().commands();
+            let linked = text!(&mut commands, [("a"), ("b")]).id();
+            app.world_mut().flush();
+
+            for parent in [children, linked] {
+                let mut commands = app.world_mut().commands();
+                let mut entity = commands.entity(parent);
+                let first = entity.insert_span_at(0, TextSpan(section("first")));
+                let last = entity.append_span(TextSpan(section("last")));
+                entity.remove_span_at(1);
+                let middle = entity.replace_span(1, TextSpan(section("middle")));
+                app.world_mut().flush();
+                app.update();
+                assert_eq!(values(&app, parent), ["first", "middle", "last"]);
+                assert_eq!(
+                    app.world().get::<SpanEntities>(parent).unwrap().entities(),
+                    [first, middle, last]
+                );
+            }
+            assert!(!app.world().entities().contains(child_spans[0]));
+            assert_eq!(
+                app.world().get::<Spans>(linked).unwrap().entities().len(),
+                3
+            );
+
+            let mut commands = app.world_mut().commands();
+            let plain = commands.entity(children).set_plain_text("plain");
+            commands.entity(linked).clear_spans();
+            app.world_mut().flush();
+            app.update();
+            assert_eq!(values(&app, children), ["plain"]);
+            assert_eq!(
+                app.world()
+                    .get::<SpanEntities>(children)
+                    .unwrap()
+                    .entities(),
+                [plain]
+            );
+            assert!(values(&app, linked).is_empty());
+
+            // Spans can't be added to entities that aren't `TextSpans` parents
+            let other = app.world_mut().spawn_empty().id();
+            let mut commands = app.world_mut().commands();
+            let orphan = commands.entity(other).append_span(TextSpan(section("a")));
+            app.world_mut().flush();
+            assert!(!app.world().entities().contains(orphan));
+        }
+
+        #[test]
+        fn removing_a_span_keeps_its_nested_spans() {
+            for linked in [false, true] {
+                let mut app = app();
+                let mut nested = Entity::PLACEHOLDER;
+                let parent = if linked {
+                    let parent = TextSpansBuilder::new()
+                        .span("a")
+                        .span("b")
+                        .spawn(app.world_mut())
+                        .id();
+                    let b = app.world().get::<Spans>(parent).unwrap().entities()[1];
+                    nested = app
+                        .world_mut()
+                        .spawn((TextSpan(section("c")), SpanOf(b)))
+                        .id();
+                    app.world_mut()
+                        .entity_mut(parent)
+                        .append_span(TextSpan(section("d")));
+                    parent
+                } else {
+                    app.world_mut()
+                        .spawn((TextBundle::default(), TextSpans))
+                        .with_children(|parent| {
+                            parent.spawn(TextSpan(section("a")));
+                            parent
+                                .spawn(TextSpan(section("b")))
+                                .with_children(|b| nested = b.spawn(TextSpan(section("c"))).id());
+                            parent.spawn(TextSpan(section("d")));
+                        })
+                        .id()
+                };
+                let mut entity = app.world_mut().entity_mut(parent);
+                assert_eq!(entity.section_spans().len(), 4);
+                let b = entity.section_spans()[1];
+                assert_eq!(entity.remove_span_at(1), Some(b));
+                assert_eq!(entity.section_spans()[1], nested);
+                app.world_mut().flush_spans();
+                assert_eq!(values(&app, parent), ["a", "c", "d"]);
+                assert!(!app.world().entities().contains(b));
+
+                let mut entity = app.world_mut().entity_mut(parent);
+                let spans = entity.section_spans();
+                assert_eq!(entity.clear_spans(), spans);
+            }
+        }
+
+        #[test]
+        fn world_api_spawns_and_edits_spans() {
+            let mut app = app();
+            let world = app.world_mut();
+            let mut parent = world.spawn_text_spans(
+                TextBundle::default(),
+                [TextSpan(section("a")), TextSpan(section("b"))],
+            );
+            let spans = parent.get::<Spans>().unwrap().entities().to_vec();
+            assert_eq!(parent.section_spans(), spans);
+
+            let first = parent.insert_span_at(0, TextSpan(section("first")));
+            let last = parent.append_span(TextSpan(section("last")));
+            assert_eq!(parent.remove_span_at(1), Some(spans[0]));
+            let middle = parent.replace_span(1, TextSpan(section("middle")));
+            assert_eq!(parent.section_spans(), [first, middle, last]);
+            assert_eq!(parent.remove_span_at(3), None);
+            let parent = parent.id();
+            assert!(!app.world().entities().contains(spans[1]));
+            app.world_mut().flush_spans();
+            assert_eq!(values(&app, parent), ["first", "middle", "last"]);
+
+            let mut entity = app.world_mut().entity_mut(parent);
+            let plain = entity.set_plain_text("plain");
+            assert_eq!(entity.section_spans(), [plain]);
+            entity.clear_spans();
+            assert!(entity.section_spans().is_empty());
+        }
+
+        #[test]
+        fn builder_spawns_spans_and_groups() {
+            #[derive(Component)]
+            struct Word(usize);
+
+            let mut app = app();
+            let words = ["one", "two", "three"];
+            let builder = words
+                .iter()
+                .enumerate()
+                .fold(TextSpansBuilder::new(), |builder, (i, &word)| {
+                    builder.span(word).with(Word(i))
+                })
+                .group(|group| {
+                    group
+                        .styled("four", TextStyle::default())
+                        .group(|group| group.span("five"))
+                })
+                .with(SpanStyle {
+                    font_size: Some(50.0),
+                    ..default()
+                });
+            let mut commands = app.world_mut().commands();
+            let parent = builder.spawn(&mut commands).id();
+            let world_parent = TextSpansBuilder::new()
+                .parent_bundle(Text2dBundle::default())
+                .span("world")
+                .spawn(app.world_mut())
+                .id();
+            app.world_mut().flush();
+            app.update();
+
+            assert_eq!(
+                values(&app, parent),
+                ["one", "two", "three", "four", "five"]
+            );
+            assert_eq!(
+                app.world().get::<Text>(parent).unwrap().sections[4]
+                    .style
+                    .font_size,
+                50.0
+            );
+            let spans = app.world().get::<SpanEntities>(parent).unwrap().entities();
+            assert_eq!(app.world().get::<Word>(spans[2]).unwrap().0, 2);
+            assert_eq!(values(&app, world_parent), ["world"]);
+            assert!(app
+                .world()
+                .get::<bevy::text::Text2dBounds>(world_parent)
+                .is_some());
+        }
+
+        #[test]
+        fn flushing_syncs_within_the_same_system() {
+            #[derive(Resource)]
+            struct Seen(Vec<String>);
+
+            let mut app = app();
+            let (parent, spans) = spawn_parent(&mut app, 2);
+            app.add_systems(Update, move |world: &mut World| {
+                world.get_mut::<TextSpan>(spans[1]).unwrap().0.value = "one".into();
+                world.flush_spans();
+                let text = world.get::<Text>(parent).unwrap();
+                let seen = text.sections.iter().map(|s| s.value.clone()).collect();
+                world.insert_resource(Seen(seen));
+            });
+            app.update();
+            assert_eq!(app.world().resource::<Seen>().0, ["0", "one"]);
+
+            let span = app.world_mut().spawn(TextSpan(section("2"))).id();
+            app.world_mut().entity_mut(parent).add_child(span);
+            FlushSpans.apply(app.world_mut());
+            assert_eq!(values(&app, parent), ["0", "one", "2"]);
+        }
+
+        #[test]
+        fn sync_runs_in_a_custom_schedule() {
+            #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+            struct CustomSync;
+
+            let mut app = App::new();
+            app.add_plugins(TsePlugin::in_schedule(CustomSync));
+            let (parent, _) = spawn_parent(&mut app, 2);
+            app.update();
+            assert!(values(&app, parent).is_empty());
+
+            app.world_mut().run_schedule(CustomSync);
+            assert_eq!(values(&app, parent), ["0", "1"]);
+        }
+
+        #[test]
+        fn backends_agree_on_structural_edits() {
+            fn run(backend: SyncBackend) -> Vec<Vec<String>> {
+                let mut app = app_with(backend);
+                let (first, spans) = spawn_parent(&mut app, 4);
+                let (second, _) = spawn_parent(&mut app, 1);
+                let mut steps = Vec::new();
+                let mut step = |app: &mut App| {
+                    app.update();
+                    steps.push(values(app, first));
+                    steps.push(values(app, second));
+                };
+                step(&mut app);
+
+                let new = app.world_mut().spawn(TextSpan(section("new"))).id();
+                app.world_mut().entity_mut(first).insert_children(1, &[new]);
+                step(&mut app);
+
+                app.world_mut().entity_mut(spans[0]).despawn_recursive();
+                app.world_mut().entity_mut(spans[1]).remove::<TextSpan>();
+                step(&mut app);
+
+                app.world_mut().entity_mut(spans[2]).set_parent(second);
+                app.world_mut()
+                    .entity_mut(spans[3])
+                    .insert(Visibility::Hidden);
+                step(&mut app);
+
+                app.world_mut().entity_mut(spans[3]).remove::<Visibility>();
+                app.world_mut().entity_mut(new).remove_parent();
+                step(&mut app);
+
+                app.world_mut().entity_mut(spans[2]).despawn_recursive();
+                app.world_mut().entity_mut(second).remove::<TextSpans>();
+                step(&mut app);
+                steps
+            }
+
+            let polling = run(SyncBackend::Polling);
+            assert_eq!(polling, run(SyncBackend::Observers));
+            assert_eq!(polling[2], ["0", "new", "1", "2", "3"]);
+            assert_eq!(polling[6], ["new"]);
+            assert_eq!(polling[7], ["0", "2"]);
+            assert_eq!(polling[8], ["3"]);
+            // No longer synced once it isn't `TextSpans`
+            assert_eq!(polling[11], ["0", "2"]);
+        }
+
+        #[test]
+        fn invalid_hierarchies_send_errors() {
+            let mut app = app();
+            let (parent, spans) = spawn_parent(&mut app, 2);
+            let decoration = app.world_mut().spawn(SpanDecoration).id();
+            app.world_mut().entity_mut(parent).add_child(decoration);
+            app.update();
+            assert!(errors(&app).is_empty());
+
+            app.world_mut().entity_mut(spans[0]).remove::<TextSpan>();
+            app.update();
+            assert_eq!(
+                errors(&app),
+                [SpanSyncError {
+                    parent,
+                    kind: SpanSyncErrorKind::MissingTextSpan { child: spans[0] },
+                }]
+            );
+
+            let bare = app
+                .world_mut()
+                .spawn(TextSpans)
+                .with_children(|parent| {
+                    parent.spawn(TextSpan(section("a")));
+                })
+                .id();
+            app.update();
+            assert_eq!(
+                errors(&app),
+                [SpanSyncError {
+                    parent: bare,
+                    kind: SpanSyncErrorKind::MissingText,
+                }]
+            );
+        }
+
+        #[test]
+        fn repair_policy_fixes_invalid_hierarchies() {
+            let mut app = App::new();
+            app.add_plugins(TsePlugin {
+                error_policy: SyncErrorPolicy::Repair,
+                ..Default::default()
+            });
+            let mut stray = Entity::PLACEHOLDER;
+            let parent = app
+                .world_mut()
+                .spawn(TextSpans)
+                .with_children(|parent| {
+                    parent.spawn(TextSpan(section("a")));
+                    stray = parent.spawn_empty().id();
+                })
+                .id();
+            app.update();
+            assert_eq!(errors(&app).len(), 1);
+            app.update();
+            assert_eq!(values(&app, parent), ["a"]);
+            assert_eq!(errors(&app).len(), 1);
+            assert!(app.world().get::<SpanDecoration>(stray).is_some());
+
+            app.world_mut().entity_mut(parent).with_children(|parent| {
+                parent.spawn(TextSpan(section("b")));
+            });
+            app.update();
+            assert_eq!(values(&app, parent), ["a", "b"]);
+            assert!(errors(&app).is_empty());
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, should_panic)]
+        fn panic_in_debug_policy() {
+            let mut app = App::new();
+            app.add_plugins(TsePlugin {
+                error_policy: SyncErrorPolicy::PanicInDebug,
+                ..Default::default()
+            });
+            app.world_mut().spawn(TextSpans).with_children(|parent| {
+                parent.spawn(TextSpan(section("a")));
+            });
+            app.update();
+        }
+
+        #[test]
+        fn text_spans_variants_insert_and_convert_text() {
+            let mut app = app();
+            let parent = app
+                .world_mut()
+                .spawn(TextSpansUi)
+                .with_children(|parent| {
+                    parent.spawn(TextSpan(section("a")));
+                })
+                .id();
+            app.update();
+            assert!(app.world().get::<Node>(parent).is_some());
+            assert!(app.world().get::<TextSpans>(parent).is_some());
+            assert_eq!(values(&app, parent), ["a"]);
+
+            app.world_mut().entity_mut(parent).insert(TextSpans2d);
+            app.update();
+            let entity = app.world().entity(parent);
+            assert!(!entity.contains::<Node>());
+            assert!(!entity.contains::<TextSpansUi>());
+            assert!(entity.contains::<bevy::text::Text2dBounds>());
+            assert_eq!(values(&app, parent), ["a"]);
+
+            // Spans keep syncing after the conversion
+            let span = app.world().get::<Children>(parent).unwrap()[0];
+            app.world_mut().get_mut::<TextSpan>(span).unwrap().0.value = "b".into();
+            app.update();
+            assert_eq!(values(&app, parent), ["b"]);
+
+            app.world_mut().entity_mut(parent).insert(TextSpansUi);
+            app.update();
+            assert!(app.world().get::<Node>(parent).is_some());
+            assert_eq!(values(&app, parent), ["b"]);
+        }
+
+        #[test]
+        fn text_spans_variants_keep_existing_components() {
+            let mut app = app();
+            let style = Style {
+                width: Val::Px(10.0),
+                ..Default::default()
+            };
+            let transform = Transform::from_xyz(1.0, 2.0, 3.0);
+            let ui = app.world_mut().spawn((TextSpansUi, style, transform)).id();
+            let world = app.world_mut().spawn((TextSpans2d, transform)).id();
+            app.update();
+            let entity = app.world().entity(ui);
+            assert!(entity.contains::<Node>());
+            assert_eq!(entity.get::<Style>().unwrap().width, Val::Px(10.0));
+            assert_eq!(*entity.get::<Transform>().unwrap(), transform);
+            let entity = app.world().entity(world);
+            assert!(entity.contains::<bevy::text::Text2dBounds>());
+            assert_eq!(*entity.get::<Transform>().unwrap(), transform);
+
+            // Converting keeps the shared components, and only strips the other variant's
+            app.world_mut().entity_mut(ui).insert(TextSpans2d);
+            app.update();
+            let entity = app.world().entity(ui);
+            assert!(!entity.contains::<Style>());
+            assert!(entity.contains::<bevy::text::Text2dBounds>());
+            assert_eq!(*entity.get::<Transform>().unwrap(), transform);
+        }
+
+        #[test]
+        fn reverse_sync_propagates_direct_text_edits() {
+            let mut app = App::new();
+            app.add_plugins(TsePlugin {
+                reverse_sync: true,
+                ..Default::default()
+            });
+            let (parent, spans) = spawn_parent(&mut app, 3);
+            app.update();
+
+            let red = Color::srgb(1.0, 0.0, 0.0);
+            {
+                let mut text = app.world_mut().get_mut::<Text>(parent).unwrap();
+                text.sections[1].value = "one".into();
+                text.sections[2].style.color = red;
+                text.sections.push(section("3"));
+            }
+            app.update();
+            assert_eq!(values(&app, parent), ["0", "one", "2", "3"]);
+            assert_eq!(
+                app.world().get::<TextSpan>(spans[1]).unwrap().0.value,
+                "one"
+            );
+            assert_eq!(
+                app.world().get::<TextSpan>(spans[2]).unwrap().0.style.color,
+                red
+            );
+            let span_entities = app.world().get::<SpanEntities>(parent).unwrap();
+            assert_eq!(span_entities.entities()[..3], spans);
+            assert_eq!(span_entities.entities().len(), 4);
+            app.update();
+            assert_eq!(values(&app, parent), ["0", "one", "2", "3"]);
+
+            app.world_mut()
+                .get_mut::<Text>(parent)
+                .unwrap()
+                .sections
+                .truncate(1);
+            app.update();
+            assert_eq!(values(&app, parent), ["0"]);
+            assert!(!app.world().entities().contains(spans[1]));
+            // The despawns are applied after this sync, so the next one catches up
+            app.update();
+            assert_eq!(values(&app, parent), ["0"]);
+            assert_eq!(
+                app.world().get::<SpanEntities>(parent).unwrap().entities(),
+                [spans[0]]
+            );
+
+            // Span edits still win over the text
+            app.world_mut()
+                .get_mut::<TextSpan>(spans[0])
+                .unwrap()
+                .0
+                .value = "zero".into();
+            app.world_mut().get_mut::<Text>(parent).unwrap().sections[0].value = "lost".into();
+            app.update();
+            assert_eq!(values(&app, parent), ["zero"]);
+            // ...and the sync's own write isn't propagated back on the next frame
+            let text = app.world().entity(parent).get_ref::<Text>().unwrap();
+            let written = app.world().resource::<SpanLayouts>().written[&parent];
+            assert_eq!(written, text.last_changed());
+        }
+
+        #[test]
+        fn span_order_sorts_siblings() {
+            let mut app = app();
+            let parent = app
+                .world_mut()
+                .spawn((TextBundle::default(), TextSpans))
+                .with_children(|parent| {
+                    parent.spawn((TextSpan(section("c")), SpanOrder(3.0)));
+                    parent.spawn((TextSpan(section("a")), SpanOrder(1.0)));
+                    parent.spawn((TextSpan(section("b")), SpanOrder(2.0)));
+                })
+                .id();
+            app.update();
+            assert_eq!(values(&app, parent), ["a", "b", "c"]);
+
+            // Appended to `Children`, but sorted between its neighbours
+            app.world_mut().entity_mut(parent).with_children(|parent| {
+                parent.spawn((TextSpan(section("ab")), SpanOrder(1.5)));
+            });
+            app.update();
+            assert_eq!(values(&app, parent), ["a", "ab", "b", "c"]);
+
+            let first = app.world().get::<SpanEntities>(parent).unwrap().entities()[0];
+            app.world_mut().get_mut::<SpanOrder>(first).unwrap().0 = 4.0;
+            app.update();
+            assert_eq!(values(&app, parent), ["ab", "b", "c", "a"]);
+        }
+
+        /// Spawns `parents` parents with 8 spans, then edits a span of each (and adds a span to
+        /// every other one) on each of `frames` frames.
+        ///
+        /// Returns the parents with their initial spans, and the time spent syncing the edits.
+        fn stress(
+            app: &mut App,
+            parents: usize,
+            frames: usize,
+        ) -> (Vec<(Entity, Vec<Entity>)>, Duration) {
+            let texts: Vec<_> = (0..parents).map(|_| spawn_parent(app, 8)).collect();
+            app.update();
+
+            let mut time = Duration::ZERO;
+            for frame in 0..frames {
+                for (i, (parent, spans)) in texts.iter().enumerate() {
+                    let span = spans[i % spans.len()];
+                    app.world_mut().get_mut::<TextSpan>(span).unwrap().0.value = frame.to_string();
+                    // Every other parent is also walked again
+                    if i % 2 == 0 {
+                        let new = app.world_mut().spawn(TextSpan(section("new"))).id();
+                        app.world_mut()
+                            .entity_mut(*parent)
+                            .insert_children(0, &[new]);
+                    }
+                }
+                app.update();
+                time += app.world().resource::<SpanLayouts>().stats.time;
+            }
+            (texts, time)
+        }
+
+        /// Stress test: many small parents changing every frame are synced in parallel,
+        /// with the same result as syncing them one by one.
+        #[test]
+        fn many_parents_sync_deterministically() {
+            let (parents, frames) = (2_000, 10);
+            let mut app = app();
+            let (texts, _) = stress(&mut app, parents, frames);
+            let threads = ComputeTaskPool::get().thread_num().max(1);
+            let tasks = app.world().resource::<SpanLayouts>().stats.tasks;
+            assert!((1..=threads).contains(&tasks), "{tasks} tasks");
+
+            for (i, (parent, spans)) in texts.iter().enumerate() {
+                let mut expected: Vec<String> = (0..8).map(|j| j.to_string()).collect();
+                expected[i % spans.len()] = (frames - 1).to_string();
+                if i % 2 == 0 {
+                    let mut new = vec!["new".to_string(); frames];
+                    new.append(&mut expected);
+                    expected = new;
+                }
+                assert_eq!(values(&app, *parent), expected);
+                let span_entities = app.world().get::<SpanEntities>(*parent).unwrap();
+                assert_eq!(&span_entities.entities()[expected.len() - 8..], spans);
+            }
+        }
+
+        /// Compares the sync time of the stress test on 1 thread and on every thread, each in
+        /// a child process, as the `ComputeTaskPool` can only be set up once per process.
+        ///
+        /// `cargo test --release -- --ignored parallel_sync_scales_with_threads --nocapture`
+        #[test]
+        #[ignore = "timing, best run in release"]
+        fn parallel_sync_scales_with_threads() {
+            const THREADS: &str = "TSE_STRESS_THREADS";
+            if let Ok(threads) = std::env::var(THREADS) {
+                let threads = threads.parse().unwrap();
+                ComputeTaskPool::get_or_init(|| {
+                    TaskPoolBuilder::new().num_threads(threads).build()
+                });
+                let (_, time) = stress(&mut app(), 2_000, 10);
+                println!("sync time: {}", time.as_nanos());
+                return;
+            }
+
+            let time = |threads: usize| {
+                let output = std::process::Command::new(std::env::current_exe().unwrap())
+                    .args(["--exact", "test::sync::parallel_sync_scales_with_threads"])
+                    .args(["--ignored", "--nocapture", "--test-threads=1"])
+                    .env(THREADS, threads.to_string())
+                    .output()
+                    .unwrap();
+                let stdout = String::from_utf8(output.stdout).unwrap();
+                let nanos = stdout
+                    .lines()
+                    .find_map(|line| Some(line.split_once("sync time: ")?.1.trim()))
+                    .unwrap_or_else(|| panic!("no sync time in {stdout}"));
+                Duration::from_nanos(nanos.parse().unwrap())
+            };
+            let threads = std::thread::available_parallelism().map_or(1, usize::from);
+            let (serial, parallel) = (time(1), time(threads));
+            println!("1 thread: {serial:?}, {threads} threads: {parallel:?}");
+            if threads > 1 {
+                assert!(parallel < serial);
+            }
+        }
+
+        #[test]
+        fn diagnostics_record_the_last_sync() {
+            use bevy::diagnostic::{DiagnosticPath, DiagnosticsStore};
+
+            let mut app = app();
+            app.add_plugins(TseDiagnosticsPlugin);
+            let measurement = |app: &App, path: &DiagnosticPath| {
+                let store = app.world().resource::<DiagnosticsStore>();
+                store.get(path).unwrap().measurement().unwrap().value
+            };
+            let (_, spans) = spawn_parent(&mut app, 3);
+            app.update();
+            assert_eq!(measurement(&app, &TseDiagnosticsPlugin::DIRTY_PARENTS), 1.0);
+            assert_eq!(
+                measurement(&app, &TseDiagnosticsPlugin::REBUILT_SECTIONS),
+                3.0
+            );
+            assert_eq!(measurement(&app, &TseDiagnosticsPlugin::CLONED_BYTES), 3.0);
+            assert_eq!(measurement(&app, &TseDiagnosticsPlugin::SPANS), 3.0);
+
+            app.world_mut()
+                .get_mut::<TextSpan>(spans[1])
+                .unwrap()
+                .0
+                .value = "one".into();
+            app.update();
+            assert_eq!(measurement(&app, &TseDiagnosticsPlugin::DIRTY_PARENTS), 0.0);
+            assert_eq!(
+                measurement(&app, &TseDiagnosticsPlugin::REBUILT_SECTIONS),
+                1.0
+            );
+            assert_eq!(measurement(&app, &TseDiagnosticsPlugin::CLONED_BYTES), 3.0);
+            assert!(measurement(&app, &TseDiagnosticsPlugin::SYNC_TIME) >= 0.0);
+        }
+
+        #[test]
+        fn validation_reports_invalid_hierarchies() {
+            let mut app = app();
+            let (valid, _) = spawn_parent(&mut app, 2);
+            app.update();
+            assert!(validate_spans(app.world_mut()).is_valid());
+
+            let orphan = app.world_mut().spawn(TextSpan(section("orphan"))).id();
+            let (no_text, _) = spawn_parent(&mut app, 1);
+            app.world_mut().entity_mut(no_text).remove::<Text>();
+            let span_parent = app.world_mut().spawn((TextSpans, TextSpan::default())).id();
+            let empty = app.world_mut().spawn(TextSpan::default()).id();
+            let stray = app.world_mut().spawn_empty().id();
+            app.world_mut()
+                .entity_mut(valid)
+                .push_children(&[empty, stray]);
+            let report = validate_spans(app.world_mut());
+            assert_eq!(
+                report.issues,
+                [
+                    SpanIssue {
+                        entity: orphan,
+                        kind: SpanIssueKind::Orphan,
+                    },
+                    SpanIssue {
+                        entity: no_text,
+                        kind: SpanIssueKind::MissingText,
+                    },
+                    SpanIssue {
+                        entity: span_parent,
+                        kind: SpanIssueKind::ParentIsSpan,
+                    },
+                    SpanIssue {
+                        entity: empty,
+                        kind: SpanIssueKind::EmptySpan,
+                    },
+                    SpanIssue {
+                        entity: stray,
+                        kind: SpanIssueKind::MissingTextSpan { parent: valid },
+                    },
+                ]
+            );
+
+            // Fonts are checked once fonts can be loaded
+            app.world_mut().init_resource::<Assets<Font>>();
+            let report = validate_spans(app.world_mut());
+            assert!(report.issues.contains(&SpanIssue {
+                entity: orphan,
+                kind: SpanIssueKind::UnloadedFont {
+                    font: Handle::<Font>::default().id(),
+                },
+            }));
+        }
+
+        /// The work done for a single changed span must not depend on the number of spans under
+        /// the parent: only its section is cloned.
+        #[test]
+        fn single_span_updates_clone_one_section_at_any_span_count() {
+            for count in [10, 100, 10_000] {
+                let mut app = app();
+                let (parent, spans) = spawn_parent(&mut app, count);
+                app.update();
+                assert_eq!(
+                    app.world().resource::<SpanLayouts>().stats.cloned_sections,
+                    count
+                );
+
+                let frames = 100;
+                for frame in 0..frames {
+                    let span = spans[count / 2];
+                    app.world_mut().get_mut::<TextSpan>(span).unwrap().0.value = frame.to_string();
+                    app.update();
+                    assert_eq!(
+                        app.world().resource::<SpanLayouts>().stats.cloned_sections,
+                        1
+                    );
+                }
+
+                let text = app.world().get::<Text>(parent).unwrap();
+                assert_eq!(text.sections.len(), count);
+                assert_eq!(text.sections[count / 2].value, (frames - 1).to_string());
+            }
+        }
+    }
 }